@@ -0,0 +1,343 @@
+//! Expected answers kept in an `answers.toml` next to a day's inputs.
+//!
+//! Tables are keyed by day and input file name, values by part:
+//!
+//! ```toml
+//! [day01.input]
+//! part1 = 1036
+//! part2 = "6228"
+//! ```
+//!
+//! Answers may be written as integers or strings, they are compared as text.
+
+use std::{
+    collections::BTreeMap,
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use eyre::{Result, eyre};
+
+use crate::{Solution, table};
+
+pub const FILE_NAME: &str = "answers.toml";
+
+/// Recorded answers, keyed by `(day, input name, part)`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, String, u8), String>,
+}
+
+impl Answers {
+    pub fn parse(text: &str) -> Result<Self> {
+        let table: toml::Table = text.parse()?;
+        let mut entries = BTreeMap::new();
+
+        for (day_key, inputs) in table {
+            let day = day_key
+                .strip_prefix("day")
+                .and_then(|d| d.parse::<u8>().ok())
+                .ok_or(eyre!("{}: expected a key like day01", day_key))?;
+            let inputs = inputs
+                .as_table()
+                .ok_or(eyre!("{}: expected a table of inputs", day_key))?;
+
+            for (input, parts) in inputs {
+                let parts = parts.as_table().ok_or(eyre!(
+                    "{}.{}: expected a table of parts",
+                    day_key,
+                    input
+                ))?;
+
+                for (part_key, answer) in parts {
+                    let part = match part_key.as_str() {
+                        "part1" => 1,
+                        "part2" => 2,
+                        _ => return Err(eyre!("{}.{}: unknown part {}", day_key, input, part_key)),
+                    };
+                    let answer = match answer {
+                        toml::Value::String(s) => s.clone(),
+                        toml::Value::Integer(n) => n.to_string(),
+                        other => {
+                            return Err(eyre!(
+                                "{}.{}.{}: answer must be an integer or string, got {}",
+                                day_key,
+                                input,
+                                part_key,
+                                other.type_str()
+                            ));
+                        }
+                    };
+                    entries.insert((day, input.clone(), part), answer);
+                }
+            }
+        }
+
+        Ok(Self { entries })
+    }
+
+    /// Answers from `dir/answers.toml`, none when the file doesn't exist.
+    pub fn load(dir: &Path) -> Result<Self> {
+        let path = dir.join(FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Self::parse(&std::fs::read_to_string(&path)?)
+            .map_err(|e| eyre!("{}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, day: u8, input: &str, part: u8) -> Option<&str> {
+        self.entries
+            .get(&(day, input.to_string(), part))
+            .map(String::as_str)
+    }
+
+    /// Names of the inputs with at least one answer for `day`.
+    pub fn inputs(&self, day: u8) -> impl Iterator<Item = &str> {
+        let mut names = self
+            .entries
+            .keys()
+            .filter(move |(d, _, _)| *d == day)
+            .map(|(_, input, _)| input.as_str())
+            .collect::<Vec<_>>();
+        names.dedup();
+        names.into_iter()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    /// No answer recorded, or no input file for a recorded answer.
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Missing => "missing",
+        })
+    }
+}
+
+/// One solver run against one input and part.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Check {
+    pub day: u8,
+    pub input: String,
+    pub part: u8,
+    pub expected: Option<String>,
+    /// The answer, or the error text of a failed run. `None` without an input file.
+    pub got: Option<Result<String, String>>,
+    /// The solver panicked, `got` holds the panic message.
+    pub panicked: bool,
+}
+
+impl Check {
+    pub fn status(&self) -> Status {
+        match (&self.expected, &self.got) {
+            _ if self.panicked => Status::Fail,
+            (Some(expected), Some(Ok(got))) if expected == got => Status::Pass,
+            (Some(_), Some(_)) => Status::Fail,
+            _ => Status::Missing,
+        }
+    }
+}
+
+/// Input files in `dir`, every `.txt` file by its stem.
+fn input_files(dir: &Path) -> Result<BTreeMap<String, PathBuf>> {
+    let mut files = BTreeMap::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| eyre!("{}: {}", dir.display(), e))? {
+        let path = entry?.path();
+        if path.extension().is_some_and(|ext| ext == "txt")
+            && let Some(stem) = path.file_stem().and_then(|s| s.to_str())
+        {
+            files.insert(stem.to_string(), path.clone());
+        }
+    }
+    Ok(files)
+}
+
+/// One part's answer or error text, and whether the solver panicked.
+pub fn run_part(solution: &dyn Solution, part: u8, input: &str) -> (Result<String, String>, bool) {
+    match panic::catch_unwind(AssertUnwindSafe(|| solution.part(part, input))) {
+        Ok(answer) => (answer.map_err(|e| e.to_string()), false),
+        Err(payload) => {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or("unknown panic".to_string());
+            (Err(format!("panicked: {}", message)), true)
+        }
+    }
+}
+
+/// Runs `solution` on every input in `dir` and every input with a recorded answer.
+pub fn verify(solution: &dyn Solution, dir: &Path) -> Result<Vec<Check>> {
+    let day = solution.day();
+    let answers = Answers::load(dir)?;
+    let files = input_files(dir)?;
+
+    let mut inputs = files.keys().cloned().collect::<Vec<_>>();
+    inputs.extend(answers.inputs(day).map(String::from));
+    inputs.sort();
+    inputs.dedup();
+
+    let mut checks = vec![];
+    for input in inputs {
+        let text = files.get(&input).map(std::fs::read_to_string).transpose()?;
+        for part in [1, 2] {
+            let run = text.as_ref().map(|text| run_part(solution, part, text));
+            checks.push(Check {
+                day,
+                expected: answers.get(day, &input, part).map(String::from),
+                panicked: run.as_ref().is_some_and(|(_, panicked)| *panicked),
+                got: run.map(|(got, _)| got),
+                input: input.clone(),
+                part,
+            });
+        }
+    }
+    Ok(checks)
+}
+
+/// Text of `dir/<input>.txt` and the answer recorded for it.
+///
+/// For solution tests, fails when either is missing. Tests of answers that
+/// aren't known yet should be `#[ignore]`d rather than skip themselves.
+pub fn recorded(dir: &Path, day: u8, input: &str, part: u8) -> Result<(String, String)> {
+    let path = dir.join(format!("{}.txt", input));
+    let text = std::fs::read_to_string(&path).map_err(|e| eyre!("{}: {}", path.display(), e))?;
+    let expected = Answers::load(dir)?
+        .get(day, input, part)
+        .map(String::from)
+        .ok_or(eyre!(
+            "no answer for day {:02} {} part {} in {}",
+            day,
+            input,
+            part,
+            dir.join(FILE_NAME).display()
+        ))?;
+    Ok((text, expected))
+}
+
+/// Checks as a table with one row per day, input and part.
+pub fn table(checks: &[Check]) -> String {
+    let rows = checks
+        .iter()
+        .map(|c| {
+            [
+                format!("{:02}", c.day),
+                c.input.clone(),
+                c.part.to_string(),
+                c.expected.clone().unwrap_or("-".to_string()),
+                match &c.got {
+                    Some(Ok(got)) => got.clone(),
+                    Some(Err(e)) => format!("error: {}", e),
+                    None => "-".to_string(),
+                },
+                c.status().to_string(),
+            ]
+        })
+        .collect::<Vec<_>>();
+
+    table::render(["day", "input", "part", "expected", "got", "status"], &rows)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct Double;
+
+    impl Solution for Double {
+        fn day(&self) -> u8 {
+            7
+        }
+
+        fn part_one(&self, input: &str) -> Result<String> {
+            Ok((input.trim().parse::<i64>()? * 2).to_string())
+        }
+
+        fn part_two(&self, input: &str) -> Result<String> {
+            if input.trim() == "5" {
+                panic!("five");
+            }
+            Err(eyre!("not yet"))
+        }
+    }
+
+    fn scratch_dir(name: &str) -> Result<PathBuf> {
+        let dir = std::env::temp_dir().join(format!("aoc-core-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    #[test]
+    fn parses_answers() -> Result<()> {
+        let answers = Answers::parse(
+            "[day01.input]\npart1 = 1036\npart2 = \"6228\"\n\n[day01.testinput]\npart1 = 3\n",
+        )?;
+        assert_eq!(answers.get(1, "input", 1), Some("1036"));
+        assert_eq!(answers.get(1, "input", 2), Some("6228"));
+        assert_eq!(answers.get(1, "testinput", 2), None);
+        assert_eq!(
+            answers.inputs(1).collect::<Vec<_>>(),
+            ["input", "testinput"]
+        );
+
+        assert!(Answers::parse("[one.input]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day01.input]\npart3 = 1").is_err());
+        assert!(Answers::parse("[day01.input]\npart1 = 1.5").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn verifies_inputs() -> Result<()> {
+        let dir = scratch_dir("verify")?;
+        std::fs::write(dir.join("input.txt"), "21\n")?;
+        std::fs::write(dir.join("other.txt"), "5\n")?;
+        std::fs::write(
+            dir.join(FILE_NAME),
+            "[day07.input]\npart1 = 42\npart2 = 1\n[day07.other]\npart1 = 11\n[day07.gone]\npart1 = 0\n",
+        )?;
+
+        let checks = verify(&Double, &dir)?;
+        let statuses = checks
+            .iter()
+            .map(|c| (c.input.as_str(), c.part, c.status()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                ("gone", 1, Status::Missing),
+                ("gone", 2, Status::Missing),
+                ("input", 1, Status::Pass),
+                ("input", 2, Status::Fail),
+                ("other", 1, Status::Fail),
+                // no answer recorded, but a panic always fails
+                ("other", 2, Status::Fail),
+            ]
+        );
+        assert_eq!(
+            recorded(&dir, 7, "other", 1)?,
+            ("5\n".to_string(), "11".to_string())
+        );
+        assert!(recorded(&dir, 7, "other", 2).is_err());
+        assert!(recorded(&dir, 7, "gone", 1).is_err());
+
+        let table = table(&checks);
+        assert!(table.starts_with("day  input  part  expected  got                    status\n"));
+        assert!(table.contains("07   input  2     1         error: not yet         fail\n"));
+        assert!(table.contains("07   other  2     -         error: panicked: five  fail\n"));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+//! Decimal digits of non-negative integers, exact where `log10` on floats isn't.
+
+use num_traits::PrimInt;
+
+fn ten<T: PrimInt>() -> T {
+    T::from(10).unwrap_or_else(T::max_value)
+}
+
+/// Number of decimal digits, 1 for zero. Negative numbers count like their absolute value.
+pub fn count<T: PrimInt>(n: T) -> u32 {
+    let mut n = n;
+    let mut count = 1;
+    while n / ten() != T::zero() {
+        n = n / ten();
+        count += 1;
+    }
+    count
+}
+
+/// Digits from most to least significant. Negative numbers give those of their absolute value.
+pub fn digits<T: PrimInt>(n: T) -> Vec<u8> {
+    let mut n = n;
+    let mut out = vec![];
+    loop {
+        let digit = (n % ten()).to_i128().unwrap_or_default().unsigned_abs() as u8;
+        out.push(digit);
+        n = n / ten();
+        if n == T::zero() {
+            break;
+        }
+    }
+    out.reverse();
+    out
+}
+
+/// Number made of the given digits, `None` on overflow or a digit above 9.
+pub fn from_digits<T: PrimInt>(digits: &[u8]) -> Option<T> {
+    digits.iter().try_fold(T::zero(), |acc, &d| {
+        if d > 9 {
+            return None;
+        }
+        acc.checked_mul(&ten())?.checked_add(&T::from(d)?)
+    })
+}
+
+/// `n` written `times` times in a row, e.g. 12 twice is 1212. `None` on overflow.
+pub fn repeat<T: PrimInt>(n: T, times: u32) -> Option<T> {
+    let shift = num_traits::checked_pow(ten::<T>(), count(n) as usize)?;
+    (0..times).try_fold(T::zero(), |acc, _| acc.checked_mul(&shift)?.checked_add(&n))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn counts() {
+        assert_eq!(count(0u8), 1);
+        assert_eq!(count(9u64), 1);
+        assert_eq!(count(10u64), 2);
+        assert_eq!(count(999_999_999_999_999u64), 15);
+        assert_eq!(count(u64::MAX), 20);
+        assert_eq!(count(-120i32), 3);
+        assert_eq!(count(i64::MIN), 19);
+    }
+
+    #[test]
+    fn round_trip() {
+        for n in [0u64, 7, 10, 1234567890, u64::MAX] {
+            assert_eq!(from_digits::<u64>(&digits(n)), Some(n));
+        }
+        assert_eq!(digits(-305i64), vec![3, 0, 5]);
+        assert_eq!(from_digits::<u8>(&[2, 5, 6]), None);
+        assert_eq!(from_digits::<u32>(&[1, 10]), None);
+        assert_eq!(from_digits::<u32>(&[]), Some(0));
+    }
+
+    #[test]
+    fn repeats() {
+        assert_eq!(repeat(12i64, 2), Some(1212));
+        assert_eq!(repeat(7u32, 3), Some(777));
+        assert_eq!(repeat(105u64, 1), Some(105));
+        assert_eq!(repeat(99u8, 2), None);
+        assert_eq!(repeat(1u64, 0), Some(0));
+    }
+}
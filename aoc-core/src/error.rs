@@ -0,0 +1,44 @@
+use std::{error::Error, fmt};
+
+/// Input that doesn't have the shape a puzzle expects.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputError {
+    /// Nothing to work with.
+    Empty,
+    /// A part of a record isn't there, e.g. the end of `3-`.
+    Missing(&'static str),
+    /// A token that isn't one of the known ones, `what` says what was expected.
+    Unknown { what: &'static str, token: String },
+    /// A grid row whose length differs from the first row.
+    Ragged {
+        row: usize,
+        width: usize,
+        expected: usize,
+    },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputError::Empty => write!(f, "empty input"),
+            InputError::Missing(what) => write!(f, "{} missing", what),
+            InputError::Unknown { what, token } => write!(f, "unknown {}: {}", what, token),
+            InputError::Ragged {
+                row,
+                width,
+                expected,
+            } => write!(f, "row {} has {} cells, expected {}", row, width, expected),
+        }
+    }
+}
+
+impl Error for InputError {}
+
+impl InputError {
+    pub fn unknown(what: &'static str, token: impl fmt::Display) -> Self {
+        Self::Unknown {
+            what,
+            token: token.to_string(),
+        }
+    }
+}
@@ -0,0 +1,337 @@
+//! Rectangular `Grid` plus whole-grid moves that tolerate rows of different lengths.
+//!
+//! For the free functions short rows count as padded on the right up to the
+//! longest row. The plain versions return the padding as `None`, the `_filled`
+//! ones put `fill` there. Any nesting of iterables works as input, e.g.
+//! `Vec<Vec<T>>`, `[[T; N]; M]` or `lines().map(str::chars)`.
+
+use std::ops::{Index, IndexMut};
+
+use eyre::Result;
+
+use crate::InputError;
+
+/// Cells stored row by row, addressed as `(y, x)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Fails when a row is longer or shorter than the first one.
+    pub fn from_rows<G, R>(rows: G) -> Result<Self, InputError>
+    where
+        G: IntoIterator<Item = R>,
+        R: IntoIterator<Item = T>,
+    {
+        let mut cells = vec![];
+        let mut width = None;
+        let mut height = 0;
+        for (row, cols) in rows.into_iter().enumerate() {
+            let before = cells.len();
+            cells.extend(cols);
+            let len = cells.len() - before;
+            match width {
+                None => width = Some(len),
+                Some(expected) if expected != len => {
+                    return Err(InputError::Ragged {
+                        row,
+                        width: len,
+                        expected,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    /// One row per line, each character mapped by `cell`.
+    pub fn parse<F>(input: &str, mut cell: F) -> Result<Self>
+    where
+        F: FnMut(char) -> Result<T>,
+    {
+        let rows = input
+            .lines()
+            .map(|line| line.chars().map(&mut cell).collect::<Result<Vec<T>>>())
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::from_rows(rows)?)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, y: usize, x: usize) -> Option<&T> {
+        (y < self.height && x < self.width).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, y: usize, x: usize) -> Option<&mut T> {
+        (y < self.height && x < self.width).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        (0..self.height).map(|y| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// Every `(y, x)` row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (y, x)))
+    }
+
+    /// The up to eight positions around `(y, x)` that lie inside the grid.
+    pub fn neighbors(&self, y: usize, x: usize) -> impl Iterator<Item = (usize, usize)> + use<T> {
+        let (height, width) = (self.height, self.width);
+        (y.saturating_sub(1)..(y + 2).min(height))
+            .flat_map(move |i| (x.saturating_sub(1)..(x + 2).min(width)).map(move |j| (i, j)))
+            .filter(move |pos| *pos != (y, x))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (y, x): (usize, usize)) -> &T {
+        assert!(
+            x < self.width,
+            "x {} out of bounds for width {}",
+            x,
+            self.width
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (y, x): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width,
+            "x {} out of bounds for width {}",
+            x,
+            self.width
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// Pads every row to the longest one.
+fn rectangle<G, R, T>(grid: G) -> Vec<Vec<Option<T>>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+{
+    let mut rows: Vec<Vec<Option<T>>> = grid
+        .into_iter()
+        .map(|row| row.into_iter().map(Some).collect())
+        .collect();
+    let width = rows.iter().map(Vec::len).max().unwrap_or(0);
+    for row in rows.iter_mut() {
+        row.resize_with(width, || None);
+    }
+    rows
+}
+
+fn fill<T: Clone>(grid: Vec<Vec<Option<T>>>, fill: T) -> Vec<Vec<T>> {
+    grid.into_iter()
+        .map(|row| {
+            row.into_iter()
+                .map(|cell| cell.unwrap_or_else(|| fill.clone()))
+                .collect()
+        })
+        .collect()
+}
+
+/// Rows become columns, `out[c][r]` is `grid[r][c]`.
+pub fn transpose<G, R, T>(grid: G) -> Vec<Vec<Option<T>>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+{
+    let rows = rectangle(grid);
+    let width = rows.first().map_or(0, Vec::len);
+    let mut iters: Vec<_> = rows.into_iter().map(Vec::into_iter).collect();
+    (0..width)
+        .map(|_| iters.iter_mut().filter_map(Iterator::next).collect())
+        .collect()
+}
+
+/// Quarter turn counter-clockwise, the last column becomes the first row.
+pub fn rotate_left<G, R, T>(grid: G) -> Vec<Vec<Option<T>>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+{
+    transpose(flip(grid)).into_iter().map(flatten).collect()
+}
+
+/// Quarter turn clockwise, the first column becomes the first row, read bottom up.
+pub fn rotate_right<G, R, T>(grid: G) -> Vec<Vec<Option<T>>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+{
+    let mut rows = rectangle(grid);
+    rows.reverse();
+    transpose(rows).into_iter().map(flatten).collect()
+}
+
+/// Mirrors left to right.
+pub fn flip<G, R, T>(grid: G) -> Vec<Vec<Option<T>>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+{
+    let mut rows = rectangle(grid);
+    for row in rows.iter_mut() {
+        row.reverse();
+    }
+    rows
+}
+
+// cells of an already padded grid are wrapped twice after another move
+fn flatten<T>(row: Vec<Option<Option<T>>>) -> Vec<Option<T>> {
+    row.into_iter().map(Option::flatten).collect()
+}
+
+pub fn transpose_filled<G, R, T>(grid: G, fill_with: T) -> Vec<Vec<T>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+    T: Clone,
+{
+    fill(transpose(grid), fill_with)
+}
+
+pub fn rotate_left_filled<G, R, T>(grid: G, fill_with: T) -> Vec<Vec<T>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+    T: Clone,
+{
+    fill(rotate_left(grid), fill_with)
+}
+
+pub fn rotate_right_filled<G, R, T>(grid: G, fill_with: T) -> Vec<Vec<T>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+    T: Clone,
+{
+    fill(rotate_right(grid), fill_with)
+}
+
+pub fn flip_filled<G, R, T>(grid: G, fill_with: T) -> Vec<Vec<T>>
+where
+    G: IntoIterator<Item = R>,
+    R: IntoIterator<Item = T>,
+    T: Clone,
+{
+    fill(flip(grid), fill_with)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn text(grid: Vec<Vec<char>>) -> Vec<String> {
+        grid.into_iter().map(String::from_iter).collect()
+    }
+
+    #[test]
+    fn grid_access() -> Result<()> {
+        let mut grid = Grid::parse("ab\ncd\nef", Ok)?;
+        assert_eq!((grid.height(), grid.width()), (3, 2));
+        assert_eq!(grid.get(2, 1), Some(&'f'));
+        assert_eq!(grid.get(1, 2), None);
+        grid[(0, 1)] = 'x';
+        assert_eq!(
+            grid.rows().map(String::from_iter).collect::<Vec<_>>(),
+            ["ax", "cd", "ef"]
+        );
+        assert_eq!(
+            grid.neighbors(0, 0).collect::<Vec<_>>(),
+            [(0, 1), (1, 0), (1, 1)]
+        );
+        assert_eq!(grid.neighbors(1, 1).count(), 5);
+        assert_eq!(grid.positions().count(), 6);
+
+        assert_eq!(
+            Grid::from_rows([vec![1, 2], vec![3]]),
+            Err(InputError::Ragged {
+                row: 1,
+                width: 1,
+                expected: 2
+            })
+        );
+        assert_eq!(Grid::<u8>::from_rows(Vec::<Vec<u8>>::new())?.height(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn ragged_moves() {
+        let lines = || "abc\nd\nef".lines().map(str::chars);
+
+        assert_eq!(text(transpose_filled(lines(), '.')), ["ade", "b.f", "c.."]);
+        assert_eq!(
+            text(rotate_left_filled(lines(), '.')),
+            ["c..", "b.f", "ade"]
+        );
+        assert_eq!(
+            text(rotate_right_filled(lines(), '.')),
+            ["eda", "f.b", "..c"]
+        );
+        assert_eq!(text(flip_filled(lines(), '.')), ["cba", "..d", ".fe"]);
+
+        assert_eq!(
+            transpose(vec![vec![1], vec![2, 3]]),
+            vec![vec![Some(1), Some(2)], vec![None, Some(3)]]
+        );
+        assert!(transpose(Vec::<Vec<u8>>::new()).is_empty());
+    }
+
+    #[test]
+    fn moves_compose() {
+        // neither Copy nor Debug
+        #[derive(Clone, PartialEq)]
+        struct Cell(String);
+
+        let dot = Cell(".".to_string());
+        let grid = || {
+            ["ab", "cde", "", "f"]
+                .map(|row| row.chars().map(|c| Cell(c.to_string())).collect::<Vec<_>>())
+        };
+        let padded = fill(rectangle(grid()), dot.clone());
+
+        let left = |g: Vec<Vec<Cell>>| rotate_left_filled(g, dot.clone());
+        assert!(left(left(left(left(padded.clone())))) == padded);
+        assert!(
+            rotate_right_filled(rotate_left_filled(grid(), dot.clone()), dot.clone()) == padded
+        );
+        assert!(transpose_filled(transpose_filled(grid(), dot.clone()), dot.clone()) == padded);
+        assert!(flip_filled(flip_filled(grid(), dot.clone()), dot.clone()) == padded);
+    }
+}
@@ -0,0 +1,30 @@
+use std::io::Read;
+
+use eyre::{Result, eyre};
+
+/// Contents of a puzzle input file, `-` reads stdin instead.
+pub fn read(path: &str) -> Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        std::io::stdin().read_to_string(&mut input)?;
+        return Ok(input);
+    }
+    std::fs::read_to_string(path).map_err(|e| eyre!("{}: {}", path, e))
+}
+
+/// Reads the input named by the first command line argument, fails with `usage` without one.
+pub fn from_args(usage: &str) -> Result<String> {
+    let path = std::env::args().nth(1).ok_or(eyre!("usage: {}", usage))?;
+    read(&path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn names_missing_file() {
+        let err = read("no/such/input.txt").unwrap_err().to_string();
+        assert!(err.starts_with("no/such/input.txt: "), "{}", err);
+    }
+}
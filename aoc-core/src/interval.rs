@@ -0,0 +1,133 @@
+use std::{error::Error, ops::RangeInclusive, str::FromStr};
+
+use eyre::{ErrReport, Result};
+
+use crate::InputError;
+
+/// Closed interval `from..=to`, empty when `from > to`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Interval<T> {
+    pub from: T,
+    pub to: T,
+}
+
+impl<T: Ord + Copy> Interval<T> {
+    pub fn new(from: T, to: T) -> Self {
+        Self { from, to }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.from > self.to
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.from <= value && value <= self.to
+    }
+
+    /// Whether the two share at least one value.
+    pub fn overlaps(&self, other: &Self) -> bool {
+        !self.intersect(other).is_empty()
+    }
+
+    /// Values in both, possibly empty.
+    pub fn intersect(&self, other: &Self) -> Self {
+        Self {
+            from: self.from.max(other.from),
+            to: self.to.min(other.to),
+        }
+    }
+}
+
+impl<T: Ord + Copy + Into<i128>> Interval<T> {
+    /// Number of values covered, exact for any 64 bit bounds.
+    pub fn len(&self) -> u128 {
+        if self.is_empty() {
+            return 0;
+        }
+        (self.to.into() - self.from.into()) as u128 + 1
+    }
+}
+
+/// `from-to`, both ends may be negative like in `-5--2`. Whitespace around either end is ignored.
+impl<T> FromStr for Interval<T>
+where
+    T: FromStr,
+    T::Err: Error + Send + Sync + 'static,
+{
+    type Err = ErrReport;
+
+    fn from_str(value: &str) -> Result<Self> {
+        let value = value.trim();
+        // a leading '-' is the sign of `from`, not the separator
+        let sep = value
+            .char_indices()
+            .skip(1)
+            .find(|(_, c)| *c == '-')
+            .map(|(idx, _)| idx);
+        let (from, to) = match sep {
+            Some(idx) => (&value[..idx], &value[idx + 1..]),
+            None => (value, ""),
+        };
+        if from.trim().is_empty() {
+            return Err(InputError::Missing("first value").into());
+        }
+        if to.trim().is_empty() {
+            return Err(InputError::Missing("second value").into());
+        }
+
+        Ok(Self {
+            from: from.trim().parse()?,
+            to: to.trim().parse()?,
+        })
+    }
+}
+
+impl<T> From<RangeInclusive<T>> for Interval<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let (from, to) = range.into_inner();
+        Self { from, to }
+    }
+}
+
+impl<T> From<Interval<T>> for RangeInclusive<T> {
+    fn from(interval: Interval<T>) -> Self {
+        interval.from..=interval.to
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses() -> Result<()> {
+        assert_eq!("11-22".parse::<Interval<u64>>()?, Interval::new(11, 22));
+        assert_eq!(" 3 - 5\n".parse::<Interval<i64>>()?, Interval::new(3, 5));
+        assert_eq!("-5--2".parse::<Interval<i64>>()?, Interval::new(-5, -2));
+        assert_eq!(
+            "7".parse::<Interval<u64>>().unwrap_err().to_string(),
+            "second value missing"
+        );
+        assert_eq!(
+            "".parse::<Interval<u64>>().unwrap_err().to_string(),
+            "first value missing"
+        );
+        assert!("1-x".parse::<Interval<u64>>().is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = Interval::new(3u64, 7);
+        let b = Interval::from(6..=10);
+
+        assert_eq!(a.len(), 5);
+        assert_eq!(Interval::new(0, u64::MAX).len(), u64::MAX as u128 + 1);
+        assert_eq!(Interval::new(5, 4).len(), 0);
+        assert!(a.contains(7) && !a.contains(8));
+        assert_eq!(a.intersect(&b), Interval::new(6, 7));
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&Interval::new(8, 9)));
+        assert_eq!(RangeInclusive::from(a), 3..=7);
+    }
+}
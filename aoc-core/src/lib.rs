@@ -0,0 +1,15 @@
+//! Helpers shared by the day crates.
+
+pub mod answers;
+pub mod digits;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod interval;
+pub mod solution;
+pub mod table;
+
+pub use error::InputError;
+pub use grid::Grid;
+pub use interval::Interval;
+pub use solution::Solution;
@@ -0,0 +1,32 @@
+use eyre::{Result, eyre};
+
+use crate::input;
+
+/// A day's puzzle, both parts solved from the raw input text.
+///
+/// Answers come back as text so days with different number types fit one registry.
+pub trait Solution: Sync {
+    /// Day of the month, 1 based.
+    fn day(&self) -> u8;
+
+    fn part_one(&self, input: &str) -> Result<String>;
+
+    fn part_two(&self, input: &str) -> Result<String>;
+
+    /// `part_one` or `part_two` by number.
+    fn part(&self, part: u8, input: &str) -> Result<String> {
+        match part {
+            1 => self.part_one(input),
+            2 => self.part_two(input),
+            _ => Err(eyre!("day {} has no part {}", self.day(), part)),
+        }
+    }
+}
+
+/// Body of a plain `dayNN <input>` binary, prints both answers.
+pub fn main(solution: &dyn Solution) -> Result<()> {
+    let input = input::from_args(&format!("day{:02} <input>", solution.day()))?;
+    println!("part one: {}", solution.part_one(&input)?);
+    println!("part two: {}", solution.part_two(&input)?);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+//! Plain text tables for terminal output.
+
+/// Left aligned columns two spaces apart, a header line first. Lines end without padding.
+pub fn render<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let header = header.map(String::from);
+    let widths: [usize; N] = std::array::from_fn(|col| {
+        std::iter::once(&header)
+            .chain(rows)
+            .map(|row| row[col].chars().count())
+            .max()
+            .unwrap_or(0)
+    });
+
+    std::iter::once(&header)
+        .chain(rows)
+        .map(|row| {
+            let line = row
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell))
+                .collect::<Vec<_>>()
+                .join("  ");
+            line.trim_end().to_string() + "\n"
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn aligns_columns() {
+        let rows = [
+            ["1".to_string(), "apple".to_string(), "".to_string()],
+            ["20".to_string(), "fig".to_string(), "x".to_string()],
+        ];
+        assert_eq!(
+            render(["n", "fruit", "note"], &rows),
+            "n   fruit  note\n1   apple\n20  fig    x\n"
+        );
+        assert_eq!(render(["only"], &[]), "only\n");
+    }
+}
@@ -0,0 +1,209 @@
+use std::time::Instant;
+
+use aoc_core::{
+    answers::{self, Status},
+    input,
+};
+use eyre::{Result, eyre};
+
+mod registry;
+
+const USAGE: &str = "usage: aoc run <day> [--part N] [--input PATH|-] [--example]
+       aoc run --all [--part N] [--example]
+       aoc verify [<day>]";
+
+#[derive(Debug, PartialEq)]
+enum Days {
+    All,
+    One(u8),
+}
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    days: Days,
+    /// Both parts when not given.
+    part: Option<u8>,
+    /// Overrides the day's own input file, `-` is stdin.
+    input: Option<String>,
+    example: bool,
+}
+
+impl RunArgs {
+    fn parse<I>(args: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let mut days = None;
+        let mut part = None;
+        let mut input = None;
+        let mut example = false;
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--all" => days = Some(Days::All),
+                "--part" => {
+                    let value = args.next().ok_or(eyre!("--part needs a number"))?;
+                    part = match value.as_str() {
+                        "1" => Some(1),
+                        "2" => Some(2),
+                        _ => return Err(eyre!("part must be 1 or 2, got {}", value)),
+                    };
+                }
+                "--input" => input = Some(args.next().ok_or(eyre!("--input needs a path"))?),
+                "--example" => example = true,
+                flag if flag.starts_with("--") => return Err(eyre!("unknown flag {}", flag)),
+                day => {
+                    if days.is_some() {
+                        return Err(eyre!("more than one day given"));
+                    }
+                    let number = day.trim_start_matches("day");
+                    days = Some(Days::One(
+                        number.parse().map_err(|_| eyre!("not a day: {}", day))?,
+                    ));
+                }
+            }
+        }
+
+        let days = days.ok_or(eyre!("{}", USAGE))?;
+        if input.is_some() && (example || days == Days::All) {
+            return Err(eyre!("--input goes with a single day and no --example"));
+        }
+
+        Ok(Self {
+            days,
+            part,
+            input,
+            example,
+        })
+    }
+}
+
+fn run(args: RunArgs) -> Result<()> {
+    let solutions = match args.days {
+        Days::All => registry::SOLUTIONS.to_vec(),
+        Days::One(day) => vec![registry::find(day).ok_or(eyre!("no solver for day {}", day))?],
+    };
+    let parts = args.part.map_or(vec![1, 2], |part| vec![part]);
+
+    let mut failed = 0;
+    for solution in &solutions {
+        let day = solution.day();
+        let path = args.input.clone().unwrap_or_else(|| {
+            registry::input_path(day, args.example)
+                .display()
+                .to_string()
+        });
+        let input = match input::read(&path) {
+            Ok(input) => input,
+            Err(e) => {
+                println!("day {:02}: {}", day, e);
+                failed += parts.len();
+                continue;
+            }
+        };
+
+        for &part in &parts {
+            let start = Instant::now();
+            // a panicking solver fails its part instead of the whole run
+            let (answer, _) = answers::run_part(*solution, part, &input);
+            let elapsed = start.elapsed();
+            match answer {
+                Ok(answer) => println!(
+                    "day {:02} part {}: {:<20} {:>12.2?}",
+                    day, part, answer, elapsed
+                ),
+                Err(e) => {
+                    failed += 1;
+                    println!("day {:02} part {}: error: {}", day, part, e);
+                }
+            }
+        }
+    }
+
+    if failed > 0 {
+        return Err(eyre!(
+            "{} of {} parts failed",
+            failed,
+            solutions.len() * parts.len()
+        ));
+    }
+    Ok(())
+}
+
+/// Checks solvers against every `answers.toml`, prints a table of the outcomes.
+fn verify(day: Option<String>) -> Result<()> {
+    let solutions = match day {
+        None => registry::SOLUTIONS.to_vec(),
+        Some(day) => {
+            let number = day
+                .trim_start_matches("day")
+                .parse()
+                .map_err(|_| eyre!("not a day: {}", day))?;
+            vec![registry::find(number).ok_or(eyre!("no solver for day {}", number))?]
+        }
+    };
+
+    let mut checks = vec![];
+    for solution in solutions {
+        checks.extend(answers::verify(
+            solution,
+            &registry::input_dir(solution.day()),
+        )?);
+    }
+    print!("{}", answers::table(&checks));
+
+    let failed = checks.iter().filter(|c| c.status() == Status::Fail).count();
+    if failed > 0 {
+        return Err(eyre!("{} of {} checks failed", failed, checks.len()));
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let mut args = std::env::args().skip(1);
+    match args.next().as_deref() {
+        Some("run") => run(RunArgs::parse(args)?),
+        Some("verify") => verify(args.next()),
+        _ => Err(eyre!("{}", USAGE)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(args: &str) -> Result<RunArgs> {
+        RunArgs::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn run_args() -> Result<()> {
+        assert_eq!(
+            parse("6 --part 2 --input -")?,
+            RunArgs {
+                days: Days::One(6),
+                part: Some(2),
+                input: Some("-".to_string()),
+                example: false,
+            }
+        );
+        assert_eq!(
+            parse("--all --example")?,
+            RunArgs {
+                days: Days::All,
+                part: None,
+                input: None,
+                example: true,
+            }
+        );
+        assert_eq!(parse("day03")?.days, Days::One(3));
+
+        assert!(parse("").is_err());
+        assert!(parse("1 2").is_err());
+        assert!(parse("1 --part 3").is_err());
+        assert!(parse("--all --input x").is_err());
+        assert!(parse("1 --verbose").is_err());
+        Ok(())
+    }
+}
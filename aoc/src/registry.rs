@@ -0,0 +1,49 @@
+use std::path::{Path, PathBuf};
+
+use aoc_core::Solution;
+
+/// Every day that has a solver, in order.
+pub static SOLUTIONS: &[&dyn Solution] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+/// Where a day keeps its inputs, `dayNN/src` in the workspace.
+pub fn input_dir(day: u8) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("day{:02}", day))
+        .join("src")
+}
+
+/// The puzzle input of a day, or its example with `example`.
+pub fn input_path(day: u8, example: bool) -> PathBuf {
+    input_dir(day).join(if example {
+        "testinput.txt"
+    } else {
+        "input.txt"
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn days_are_unique_and_have_inputs() {
+        for (idx, solution) in SOLUTIONS.iter().enumerate() {
+            assert_eq!(solution.day() as usize, idx + 1);
+            assert!(input_path(solution.day(), true).exists());
+        }
+        assert!(find(6).is_some());
+        assert!(find(25).is_none());
+    }
+}
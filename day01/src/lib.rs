@@ -0,0 +1,203 @@
+use aoc_core::{InputError, Solution};
+use eyre::Result;
+
+#[derive(Debug, Clone, Copy)]
+enum Direction {
+    Left,
+    Right,
+}
+
+impl TryFrom<&str> for Direction {
+    type Error = eyre::ErrReport;
+
+    fn try_from(value: &str) -> Result<Self> {
+        match value {
+            "L" => Ok(Direction::Left),
+            "R" => Ok(Direction::Right),
+            _ => Err(InputError::unknown("direction", value).into()),
+        }
+    }
+}
+
+struct Lock {
+    value: i32,
+}
+
+impl Lock {
+    fn new() -> Self {
+        Lock { value: 50 }
+    }
+
+    fn mv(&mut self, dir: Direction, value: i32) -> i32 {
+        let dst = ((match dir {
+            Direction::Left => self.value - value,
+            Direction::Right => self.value + value,
+        }) + 100)
+            % 100;
+
+        self.value = dst;
+        dst
+    }
+
+    fn mv_counting(&mut self, dir: Direction, value: i32) -> i32 {
+        let mut wraps = i32::abs(value / 100); // 100 any direction will always go through 0
+        let relative_move = value % 100;
+
+        let mut dst = match dir {
+            Direction::Left => self.value - relative_move,
+            Direction::Right => self.value + relative_move,
+        };
+
+        if (self.value != 0 && dst <= 0) || dst > 99 {
+            wraps += 1;
+        }
+
+        dst = (dst + 100) % 100;
+
+        self.value = dst;
+        wraps
+    }
+}
+
+fn parse_moves(input: &str) -> Result<Vec<(Direction, i32)>> {
+    input
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            let (dir_str, num_str) = line.split_at(1);
+            Ok((Direction::try_from(dir_str)?, num_str.parse()?))
+        })
+        .collect()
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn part_one(&self, input: &str) -> Result<String> {
+        let mut lock = Lock::new();
+        let zeros = parse_moves(input)?
+            .into_iter()
+            .filter(|(dir, value)| lock.mv(*dir, *value) == 0)
+            .count();
+        Ok(zeros.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Result<String> {
+        let mut lock = Lock::new();
+        let wraps: i32 = parse_moves(input)?
+            .into_iter()
+            .map(|(dir, value)| lock.mv_counting(dir, value))
+            .sum();
+        Ok(wraps.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use aoc_core::answers;
+
+    use super::*;
+
+    const SRC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+    #[test]
+    fn test_lock_movement() {
+        let mut lock = Lock::new();
+        assert_eq!(lock.mv(Direction::Left, 10), 40);
+        assert_eq!(lock.mv(Direction::Right, 10), 50);
+    }
+
+    #[test]
+    fn test_test_data_part_1() {
+        let mut lock = Lock::new();
+        let test_data = include_str!("testinput.txt");
+
+        let res = test_data
+            .lines()
+            .map(|line| {
+                let (dir_str, num_str) = line.split_at(1);
+
+                let dir = Direction::try_from(dir_str).unwrap();
+                let value = num_str.parse::<i32>().unwrap();
+
+                (dir, value)
+            })
+            .map(|(dir, value)| lock.mv(dir, value))
+            .filter(|elem| *elem == 0)
+            .count() as i32;
+
+        assert_eq!(res, 3);
+    }
+
+    #[test]
+    fn test_test_data_part_2() {
+        let mut lock = Lock::new();
+        let test_data = include_str!("testinput.txt");
+
+        let res: i32 = test_data
+            .lines()
+            .map(|line| {
+                let (dir_str, num_str) = line.split_at(1);
+
+                let dir = Direction::try_from(dir_str).unwrap();
+                let value = num_str.parse::<i32>().unwrap();
+
+                (dir, value)
+            })
+            .map(|(dir, value)| lock.mv_counting(dir, value))
+            .sum();
+
+        assert_eq!(res, 6);
+    }
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let mut l = Lock::new();
+
+        let (input, expected) = answers::recorded(Path::new(SRC), 1, "input", 1)?;
+        let res: i32 = input
+            .lines()
+            .map(|line| {
+                let (dir_str, num_str) = line.split_at(1);
+
+                let dir = Direction::try_from(dir_str).unwrap();
+                let value = num_str.parse::<i32>().unwrap();
+
+                (dir, value)
+            })
+            .map(|(dir, value)| l.mv(dir, value))
+            .filter(|elem| *elem == 0)
+            .count() as i32;
+
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_solution() -> Result<()> {
+        let mut l = Lock::new();
+
+        let (input, expected) = answers::recorded(Path::new(SRC), 1, "input", 2)?;
+        let res: i32 = input
+            .lines()
+            .map(|line| {
+                let (dir_str, num_str) = line.split_at(1);
+
+                let dir = Direction::try_from(dir_str).unwrap();
+                let value = num_str.parse::<i32>().unwrap();
+
+                (dir, value)
+            })
+            .map(|(dir, value)| l.mv_counting(dir, value))
+            .sum();
+
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+}
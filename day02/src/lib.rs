@@ -0,0 +1,185 @@
+use aoc_core::{Interval, Solution, digits};
+use eyre::{Result, eyre};
+use itertools::Itertools;
+
+/// `None` when the id doesn't fit an i64, so it can't be in any range either.
+fn invalid_id_from_half(half: i64) -> Option<i64> {
+    digits::repeat(half, 2)
+}
+
+fn get_ord(num: i64) -> usize {
+    digits::count(num) as usize - 1
+}
+
+fn get_invalid_ids_part1(ids: &Interval<i64>) -> Vec<i64> {
+    let ord_from = get_ord(ids.from);
+    let ord_to = get_ord(ids.to);
+
+    (ids.from / i64::pow(10, ((ord_from / 2) + 1) as u32)
+        ..=(ids.to / i64::pow(10, ((ord_to / 2) + (ord_to % 2)) as u32)))
+        .filter_map(|elem| {
+            let invalid_id = invalid_id_from_half(elem)?;
+
+            // for odd ords like 12345 the id will be 12 and 12 falls out of this range
+            ids.contains(invalid_id).then_some(invalid_id)
+        })
+        .collect()
+}
+
+fn get_invalid_ids_part2(ids: &Interval<i64>) -> Result<Vec<i64>> {
+    let ord_from = get_ord(ids.from);
+    let ord_to = get_ord(ids.to);
+
+    let divisors: Vec<i32> = (1..ord_from + 1)
+        .filter_map(|e| (ord_from + 1).is_multiple_of(e).then_some(e as i32))
+        .chain((1..ord_to + 1).filter_map(|e| (ord_to + 1).is_multiple_of(e).then_some(e as i32)))
+        .unique()
+        .collect();
+
+    let mut all_guesses = vec![];
+
+    for divisor in divisors {
+        let digits: Vec<char> = "0123456789".chars().collect();
+
+        let parts = std::iter::repeat_with(|| digits.iter().copied())
+            .take(divisor as usize)
+            .multi_cartesian_product()
+            .filter_map(|elem| {
+                let s = String::from_iter(elem);
+                (!s.starts_with("0")).then_some(s)
+            })
+            // .map(|elem| elem.repeat(ord_to + 1 / divisor as usize))
+            .collect::<Vec<String>>();
+
+        for ord in ord_from..=ord_to + 1 {
+            if ord == divisor as usize
+                || (ord) % divisor as usize != 0
+                || (ord > 2 && ord % 2 == 0 && divisor == 1)
+            // could deduplicate further by reducing divisors that are also divisors of larger divisors of ord. it's 3am already though so nope
+            {
+                continue;
+            }
+
+            all_guesses.extend(
+                parts
+                    .iter()
+                    .map(|elem| elem.repeat((ord) / divisor as usize))
+                    .filter(|elem| !elem.is_empty()),
+            );
+        }
+    }
+    let mut invalid = vec![];
+    for guess in all_guesses.iter().unique() {
+        let id = guess
+            .parse::<i64>()
+            .map_err(|e| eyre!("guess {} for {:?}: {}", guess, ids, e))?;
+        if ids.contains(id) {
+            invalid.push(id);
+        }
+    }
+    Ok(invalid)
+}
+
+pub struct Day02;
+
+fn parse_ranges(input: &str) -> Result<Vec<Interval<i64>>> {
+    input.split(',').map(str::parse).collect()
+}
+
+impl Solution for Day02 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn part_one(&self, input: &str) -> Result<String> {
+        let sum = parse_ranges(input)?
+            .iter()
+            .flat_map(get_invalid_ids_part1)
+            .sum::<i64>();
+        Ok(sum.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Result<String> {
+        let sum = parse_ranges(input)?
+            .iter()
+            .map(get_invalid_ids_part2)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .unique()
+            .sum::<i64>();
+        Ok(sum.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{error::Error, path::Path};
+
+    use aoc_core::answers;
+
+    use super::*;
+
+    const SRC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+    #[test]
+    fn test_get_invalid_ids_part1() -> Result<(), Box<dyn Error>> {
+        let input = include_str!("testinput.txt");
+
+        let res = parse_ranges(input)?
+            .iter()
+            .flat_map(get_invalid_ids_part1)
+            .sum::<i64>();
+
+        assert_eq!(res, 1227775554);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_invalid_ids_part2() -> Result<(), Box<dyn Error>> {
+        let input = include_str!("testinput.txt");
+
+        let res = parse_ranges(input)?
+            .iter()
+            .map(get_invalid_ids_part2)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .unique()
+            .sum::<i64>();
+
+        assert_eq!(res, 4174379265);
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let (input, expected) = answers::recorded(Path::new(SRC), 2, "input", 1)?;
+
+        let res = parse_ranges(&input)?
+            .iter()
+            .flat_map(get_invalid_ids_part1)
+            .sum::<i64>();
+
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_solution() -> Result<()> {
+        let (input, expected) = answers::recorded(Path::new(SRC), 2, "input", 2)?;
+
+        let res = parse_ranges(&input)?
+            .iter()
+            .map(get_invalid_ids_part2)
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .flatten()
+            .unique()
+            .sum::<i64>();
+
+        assert_eq!(res.to_string(), expected);
+
+        Ok(())
+    }
+}
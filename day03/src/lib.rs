@@ -0,0 +1,165 @@
+use aoc_core::{Solution, digits};
+use eyre::Result;
+use itertools::Itertools;
+
+#[derive(Debug)]
+struct BatteryBank {
+    batteries: Vec<u8>,
+}
+
+impl From<&str> for BatteryBank {
+    fn from(value: &str) -> Self {
+        Self {
+            batteries: value
+                .chars()
+                .filter_map(|c| c.to_digit(10))
+                .map(|d| d as u8)
+                .collect(),
+        }
+    }
+}
+
+impl BatteryBank {
+    fn find_strongest_batteries_2(&self) -> Result<u8> {
+        self.batteries
+            .iter()
+            .enumerate()
+            .cartesian_product(self.batteries.clone().iter().enumerate())
+            .filter(|((i, _), (j, _))| i < j)
+            .map(|((_, x), (_, y))| x * 10 + y)
+            .max()
+            .ok_or(eyre::format_err!(
+                "couldn't find 2 digits among {} digits",
+                self.batteries.len()
+            ))
+    }
+
+    fn find_strongest_batteries_12(&self) -> Result<u64> {
+        let mut result_battery = vec![];
+        let bank_size = self.batteries.len();
+        let mut cutoff = None;
+        for cell_level in (0..=9).rev() {
+            let mut cells_for_level = self
+                .batteries
+                .iter()
+                .enumerate()
+                .filter(|(_, cell)| **cell == cell_level)
+                .filter(|(idx, _)| *idx >= cutoff.unwrap_or(0))
+                .collect::<Vec<(usize, &u8)>>();
+
+            if cutoff.is_some() {
+                cells_for_level.reverse();
+            }
+
+            for (level_cell_bank_idx, level_cell) in cells_for_level.iter() {
+                let numbers_to_the_right = bank_size - level_cell_bank_idx - 1;
+                // once we lock onto the biggest num that can be the first digit of a 12digit num, only consider cells
+                // to the right
+                if numbers_to_the_right >= 11 && cutoff.is_none() {
+                    cutoff = Some(*level_cell_bank_idx);
+                }
+
+                let mut inserted = false;
+
+                for (res_idx, (result_cell_bank_idx, _)) in result_battery.iter().enumerate() {
+                    if *level_cell_bank_idx < *result_cell_bank_idx {
+                        result_battery.insert(res_idx, (*level_cell_bank_idx, *level_cell));
+                        inserted = true;
+                        break;
+                    }
+                }
+
+                if !inserted {
+                    result_battery.push((*level_cell_bank_idx, *level_cell));
+                }
+
+                if result_battery.len() >= 12 {
+                    let cells = result_battery.iter().map(|(_, cell)| **cell).collect_vec();
+                    return digits::from_digits(&cells)
+                        .ok_or(eyre::format_err!("{:?} is not a u64", cells));
+                }
+            }
+        }
+
+        Err(eyre::format_err!(
+            "couldn't find 12 digits among {} digits (math broke)",
+            bank_size
+        ))
+    }
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn part_one(&self, input: &str) -> Result<String> {
+        let sum = input
+            .lines()
+            .map(BatteryBank::from)
+            .map(|bank| bank.find_strongest_batteries_2().map(u32::from))
+            .sum::<Result<u32>>()?;
+        Ok(sum.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Result<String> {
+        let sum = input
+            .lines()
+            .map(BatteryBank::from)
+            .map(|bank| bank.find_strongest_batteries_12())
+            .collect::<Result<Vec<u64>>>()?
+            .iter()
+            .sum::<u64>();
+        Ok(sum.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use aoc_core::answers;
+
+    use super::*;
+
+    const SRC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let (input, expected) = answers::recorded(Path::new(SRC), 3, "input", 1)?;
+        let res: u32 = input
+            .lines()
+            .map(BatteryBank::from)
+            .map(|bank| bank.find_strongest_batteries_2().map(u32::from))
+            .sum::<Result<u32>>()?;
+
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn short_bank() {
+        assert!(BatteryBank::from("7").find_strongest_batteries_2().is_err());
+        assert!(
+            BatteryBank::from("12345")
+                .find_strongest_batteries_12()
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn part_two_example() -> Result<()> {
+        let res = include_str!("testinput.txt")
+            .lines()
+            .map(BatteryBank::from)
+            .map(|bank| bank.find_strongest_batteries_12())
+            .collect::<Result<Vec<u64>>>()?
+            .iter()
+            .sum::<u64>();
+
+        assert_eq!(res, 3121910778619);
+        Ok(())
+    }
+}
@@ -0,0 +1,308 @@
+use eyre::{Result, eyre};
+
+use crate::{Item, Room};
+
+const WORD_BITS: usize = u64::BITS as usize;
+
+/// A `Room` with every row packed into `u64` words, one bit per cell (set = paper).
+///
+/// Bits past `size_x` in the last word of a row are always kept clear, so
+/// whole-word operations never see phantom rolls.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BitRoom {
+    size_x: usize,
+    words_per_row: usize,
+    rows: Vec<Vec<u64>>,
+}
+
+impl BitRoom {
+    pub fn new(size_y: usize, size_x: usize) -> Self {
+        let words_per_row = size_x.div_ceil(WORD_BITS);
+        Self {
+            size_x,
+            words_per_row,
+            rows: vec![vec![0; words_per_row]; size_y],
+        }
+    }
+
+    pub fn from_lines<I>(iter: I) -> Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        // parsed straight into words, going through `Room` would cost a byte per cell
+        let mut size_x = None;
+        let mut rows = vec![];
+
+        for (y, line) in iter.into_iter().enumerate() {
+            let mut row = vec![];
+            let mut len = 0;
+
+            for c in line.chars() {
+                if len % WORD_BITS == 0 {
+                    row.push(0);
+                }
+                if Item::try_from(c)? == Item::Paper {
+                    row[len / WORD_BITS] |= 1 << (len % WORD_BITS);
+                }
+                len += 1;
+            }
+
+            if *size_x.get_or_insert(len) != len {
+                return Err(eyre!("ragged room, row {} has {} cells", y, len));
+            }
+            rows.push(row);
+        }
+
+        let size_x = size_x.ok_or(eyre!("board empty"))?;
+        Ok(Self {
+            size_x,
+            words_per_row: size_x.div_ceil(WORD_BITS),
+            rows,
+        })
+    }
+
+    pub fn get_size_y(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get_size_x(&self) -> usize {
+        self.size_x
+    }
+
+    pub fn get(&self, y: usize, x: usize) -> Option<bool> {
+        if x >= self.size_x {
+            return None;
+        }
+        let word = self.rows.get(y)?[x / WORD_BITS];
+        Some(word >> (x % WORD_BITS) & 1 == 1)
+    }
+
+    /// `None` when the cell is out of bounds, like `get`.
+    pub fn set(&mut self, y: usize, x: usize, paper: bool) -> Option<()> {
+        if x >= self.size_x {
+            return None;
+        }
+        let word = &mut self.rows.get_mut(y)?[x / WORD_BITS];
+        let mask = 1 << (x % WORD_BITS);
+        if paper {
+            *word |= mask;
+        } else {
+            *word &= !mask;
+        }
+        Some(())
+    }
+
+    pub fn paper_count(&self) -> usize {
+        self.rows
+            .iter()
+            .flatten()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    /// Bitmask of rolls with fewer than four paper neighbours, same layout as the room.
+    pub fn accessible(&self) -> Vec<Vec<u64>> {
+        let empty = vec![0; self.words_per_row];
+
+        (0..self.get_size_y())
+            .map(|y| {
+                let above = if y > 0 { &self.rows[y - 1] } else { &empty };
+                let below = self.rows.get(y + 1).unwrap_or(&empty);
+                let row = &self.rows[y];
+
+                (0..self.words_per_row)
+                    .map(|i| {
+                        let inputs = [
+                            shifted_east(above, i),
+                            above[i],
+                            shifted_west(above, i),
+                            shifted_east(row, i),
+                            shifted_west(row, i),
+                            shifted_east(below, i),
+                            below[i],
+                            shifted_west(below, i),
+                        ];
+
+                        // bit-sliced counter; bit2 saturates once four neighbours are seen
+                        let (mut bit0, mut bit1, mut bit2) = (0u64, 0u64, 0u64);
+                        for input in inputs {
+                            let carry0 = bit0 & input;
+                            bit0 ^= input;
+                            let carry1 = bit1 & carry0;
+                            bit1 ^= carry0;
+                            bit2 |= carry1;
+                        }
+
+                        row[i] & !bit2
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn accessible_count(&self) -> usize {
+        self.accessible()
+            .iter()
+            .flatten()
+            .map(|w| w.count_ones() as usize)
+            .sum()
+    }
+
+    /// Removes all currently accessible rolls at once and returns how many there were.
+    pub fn remove_accessible(&mut self) -> usize {
+        let accessible = self.accessible();
+        let mut removed = 0;
+
+        for (row, mask) in self.rows.iter_mut().zip(accessible) {
+            for (word, mask) in row.iter_mut().zip(mask) {
+                removed += mask.count_ones() as usize;
+                *word &= !mask;
+            }
+        }
+
+        removed
+    }
+
+    /// Keeps removing accessible rolls until none are left and returns the total removed.
+    pub fn remove_all_accessible(&mut self) -> usize {
+        let mut total = 0;
+        loop {
+            let removed = self.remove_accessible();
+            if removed == 0 {
+                return total;
+            }
+            total += removed;
+        }
+    }
+}
+
+// each bit holds the cell to its west, i.e. bit x holds cell x - 1
+fn shifted_east(row: &[u64], i: usize) -> u64 {
+    let carry = if i > 0 {
+        row[i - 1] >> (WORD_BITS - 1)
+    } else {
+        0
+    };
+    row[i] << 1 | carry
+}
+
+// each bit holds the cell to its east, i.e. bit x holds cell x + 1
+fn shifted_west(row: &[u64], i: usize) -> u64 {
+    let carry = row.get(i + 1).map_or(0, |w| w << (WORD_BITS - 1));
+    row[i] >> 1 | carry
+}
+
+impl TryFrom<&Room> for BitRoom {
+    type Error = eyre::ErrReport;
+
+    fn try_from(room: &Room) -> Result<Self> {
+        if !room.palette().is_default() {
+            return Err(eyre!("bit-packed rooms only hold plain floor and paper"));
+        }
+
+        let size_y = room.get_size_y();
+        let size_x = room.get_size_x()?;
+        let mut out = Self::new(size_y, size_x);
+
+        for y in 0..size_y {
+            for x in 0..size_x {
+                let item = room
+                    .get_item_at(y, x)
+                    .ok_or(eyre!("ragged room, row {} is too short", y))?;
+                if item == Item::Paper {
+                    out.set(y, x, true)
+                        .ok_or(eyre!("cell out of bounds: {} {}", y, x))?;
+                }
+            }
+        }
+
+        Ok(out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::recorded_input;
+
+    fn random_lines(rng: &mut StdRng, size_y: usize, size_x: usize) -> Vec<String> {
+        let density = rng.random_range(0.1..0.9);
+        (0..size_y)
+            .map(|_| {
+                (0..size_x)
+                    .map(|_| if rng.random_bool(density) { '@' } else { '.' })
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn example() -> Result<()> {
+        let mut room = BitRoom::from_lines(
+            include_str!("testinput.txt")
+                .lines()
+                .map(|line| line.to_string()),
+        )?;
+
+        assert_eq!(room.accessible_count(), 13);
+        assert_eq!(room.remove_all_accessible(), 43);
+        Ok(())
+    }
+
+    #[test]
+    fn get_and_set_bounds() {
+        let mut room = BitRoom::new(2, 70);
+        assert_eq!(room.set(1, 69, true), Some(()));
+        assert_eq!(room.get(1, 69), Some(true));
+        assert_eq!(room.set(1, 70, true), None);
+        assert_eq!(room.set(2, 0, true), None);
+        assert_eq!(room.get(2, 0), None);
+        assert_eq!(room.paper_count(), 1);
+    }
+
+    #[test]
+    fn matches_room_on_random_grids() -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(4);
+
+        // widths around word boundaries are the interesting ones
+        for size_x in [1, 2, 63, 64, 65, 127, 128, 129, 200] {
+            for size_y in [1, 2, 3, 17] {
+                let lines = random_lines(&mut rng, size_y, size_x);
+                let mut room = Room::from_lines(lines.clone())?;
+                let mut bits = BitRoom::from_lines(lines)?;
+
+                loop {
+                    let expected = room.remove_accessible()?;
+                    let removed = bits.remove_accessible();
+                    assert_eq!(removed, expected.len(), "{size_y}x{size_x}");
+                    assert_eq!(BitRoom::try_from(&room)?, bits);
+
+                    if removed == 0 {
+                        break;
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let (lines, expected) = recorded_input(1)?;
+        let room = BitRoom::from_lines(lines)?;
+
+        assert_eq!(room.accessible_count().to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_solution() -> Result<()> {
+        let (lines, expected) = recorded_input(2)?;
+        let mut room = BitRoom::from_lines(lines)?;
+
+        assert_eq!(room.remove_all_accessible().to_string(), expected);
+        Ok(())
+    }
+}
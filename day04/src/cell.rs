@@ -0,0 +1,256 @@
+use aoc_core::InputError;
+use eyre::{Result, eyre};
+
+use crate::Item;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellKind {
+    pub symbol: char,
+    pub name: String,
+    /// Whether the cell counts towards a roll's neighbour count.
+    pub counts_as_neighbor: bool,
+    /// Whether a forklift may take the cell away once it is accessible.
+    pub removable: bool,
+}
+
+impl CellKind {
+    pub fn new(symbol: char, name: &str, counts_as_neighbor: bool, removable: bool) -> Self {
+        Self {
+            symbol,
+            name: name.to_string(),
+            counts_as_neighbor,
+            removable,
+        }
+    }
+
+    fn from_line(line: &str) -> Result<Self> {
+        let mut tokens = line.split_whitespace();
+
+        let symbol = tokens.next().ok_or(eyre!("symbol missing"))?;
+        let mut symbol_chars = symbol.chars();
+        let symbol = match (symbol_chars.next(), symbol_chars.next()) {
+            (Some(c), None) => c,
+            _ => return Err(eyre!("symbol must be a single char: {}", symbol)),
+        };
+        let name = tokens.next().ok_or(eyre!("name missing"))?;
+
+        let mut kind = Self::new(symbol, name, false, false);
+        for flag in tokens {
+            match flag {
+                "neighbor" => kind.counts_as_neighbor = true,
+                "removable" => kind.removable = true,
+                _ => return Err(eyre!("unknown flag: {}", flag)),
+            }
+        }
+
+        Ok(kind)
+    }
+}
+
+/// Maps grid chars to cell kinds.
+///
+/// `floor` and `paper` are always present, removed cells turn into floor.
+/// Any other kinds are reachable through `Item::Other` by their index.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellPalette {
+    floor: CellKind,
+    paper: CellKind,
+    others: Vec<CellKind>,
+}
+
+impl Default for CellPalette {
+    fn default() -> Self {
+        Self {
+            floor: CellKind::new('.', "floor", false, false),
+            paper: CellKind::new('@', "paper", true, true),
+            others: vec![],
+        }
+    }
+}
+
+impl CellPalette {
+    /// Default palette extended with the usual warehouse fixtures.
+    pub fn warehouse() -> Self {
+        let mut palette = Self::default();
+        for kind in [
+            CellKind::new('#', "wall", false, false),
+            CellKind::new('O', "pillar", true, false),
+            CellKind::new('D', "dock", false, false),
+            CellKind::new('%', "goods", true, false),
+        ] {
+            palette.push(kind).expect("warehouse symbols don't collide");
+        }
+        palette
+    }
+
+    /// Parses a palette file, one kind per line: `<symbol> <name> [neighbor] [removable]`.
+    ///
+    /// Kinds named `floor` and `paper` replace the built-in ones, blank lines
+    /// and lines starting with `//` are skipped.
+    pub fn from_input(value: &str) -> Result<Self> {
+        let mut palette = Self::default();
+
+        for (idx, line) in value.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with("//") {
+                continue;
+            }
+
+            let kind =
+                CellKind::from_line(line).map_err(|e| eyre!("palette line {}: {}", idx + 1, e))?;
+
+            match kind.name.as_str() {
+                // removed rolls turn into floor, a removable or counted floor would never settle
+                "floor" if kind.removable || kind.counts_as_neighbor => {
+                    return Err(eyre!(
+                        "palette line {}: floor can't be removable or a neighbor",
+                        idx + 1
+                    ));
+                }
+                "floor" => palette.floor = kind,
+                "paper" => palette.paper = kind,
+                _ => {
+                    palette
+                        .push(kind)
+                        .map_err(|e| eyre!("palette line {}: {}", idx + 1, e))?;
+                }
+            }
+        }
+
+        let symbols = palette.kinds().map(|k| k.symbol).collect::<Vec<_>>();
+        for (i, symbol) in symbols.iter().enumerate() {
+            if symbols[..i].contains(symbol) {
+                return Err(eyre!("symbol {} used more than once", symbol));
+            }
+        }
+
+        Ok(palette)
+    }
+
+    pub fn push(&mut self, kind: CellKind) -> Result<Item> {
+        if self.kinds().any(|k| k.symbol == kind.symbol) {
+            return Err(eyre!("symbol {} used more than once", kind.symbol));
+        }
+
+        let idx = u8::try_from(self.others.len()).map_err(|_| eyre!("too many cell kinds"))?;
+        self.others.push(kind);
+        Ok(Item::Other(idx))
+    }
+
+    pub fn kinds(&self) -> impl Iterator<Item = &CellKind> {
+        [&self.floor, &self.paper].into_iter().chain(&self.others)
+    }
+
+    /// The kind behind `item`, `None` for an `Item::Other` this palette doesn't have.
+    pub fn kind(&self, item: Item) -> Option<&CellKind> {
+        match item {
+            Item::Floor => Some(&self.floor),
+            Item::Paper => Some(&self.paper),
+            Item::Other(idx) => self.others.get(idx as usize),
+        }
+    }
+
+    pub fn item(&self, symbol: char) -> Result<Item> {
+        if symbol == self.floor.symbol {
+            return Ok(Item::Floor);
+        }
+        if symbol == self.paper.symbol {
+            return Ok(Item::Paper);
+        }
+
+        self.others
+            .iter()
+            .position(|k| k.symbol == symbol)
+            .map(|idx| Item::Other(idx as u8))
+            .ok_or(InputError::unknown("grid char", symbol).into())
+    }
+
+    pub fn symbol(&self, item: Item) -> Option<char> {
+        self.kind(item).map(|k| k.symbol)
+    }
+
+    /// True when the palette behaves exactly like the plain `.`/`@` grid.
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Room;
+
+    const WAREHOUSE: &str = "\
+#########
+#..@@@O.#
+D.@@%@@.#
+#.@@@@..#
+#########";
+
+    fn room(input: &str, palette: &CellPalette) -> Result<Room> {
+        Room::from_lines_with_palette(input.lines().map(|l| l.to_string()), palette)
+    }
+
+    #[test]
+    fn parse_palette_file() -> Result<()> {
+        let palette = CellPalette::from_input(
+            "// custom symbols\n\
+             _ floor\n\
+             R paper neighbor removable\n\
+             \n\
+             X crate neighbor\n",
+        )?;
+
+        assert_eq!(palette.item('_')?, Item::Floor);
+        assert_eq!(palette.item('R')?, Item::Paper);
+        assert_eq!(palette.item('X')?, Item::Other(0));
+        assert_eq!(palette.symbol(Item::Other(0)), Some('X'));
+        assert!(
+            palette
+                .kind(Item::Other(0))
+                .is_some_and(|k| k.counts_as_neighbor)
+        );
+        assert!(palette.kind(Item::Other(0)).is_some_and(|k| !k.removable));
+        assert_eq!(palette.kind(Item::Other(1)), None);
+        assert!(palette.item('@').is_err());
+
+        assert!(CellPalette::from_input("XY wall").is_err());
+        assert!(CellPalette::from_input("# wall sticky").is_err());
+        assert!(CellPalette::from_input("# wall\n# pillar").is_err());
+        assert!(CellPalette::from_input(". floor removable").is_err());
+        assert!(CellPalette::from_input(". floor neighbor").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn display_round_trip() -> Result<()> {
+        let palette = CellPalette::warehouse();
+        let parsed = room(WAREHOUSE, &palette)?;
+
+        assert_eq!(parsed.to_string().trim_end(), WAREHOUSE);
+        assert_eq!(room(&parsed.to_string(), &palette)?, parsed);
+        assert!(room(WAREHOUSE, &CellPalette::default()).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn neighbor_and_removable_flags() -> Result<()> {
+        let mut room = room(WAREHOUSE, &CellPalette::warehouse())?;
+
+        // the pillar and goods count as neighbours, but are never taken away
+        assert_eq!(room.neighbor_paper_count(1, 5)?, 5);
+        assert_eq!(room.accessible_rolls()?, vec![(3, 2)]);
+
+        while !room.remove_accessible()?.is_empty() {}
+        assert_eq!(
+            room.to_string().trim_end(),
+            "\
+#########
+#.....O.#
+D...%...#
+#.......#
+#########"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::HashSet;
+
+use eyre::Result;
+
+use crate::{Item, Room};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Neighborhood {
+    /// Up, down, left and right.
+    Four,
+    /// Diagonals too, same as the neighbour count uses.
+    Eight,
+}
+
+impl Neighborhood {
+    fn offsets(&self) -> &'static [(isize, isize)] {
+        match self {
+            Self::Four => &[(-1, 0), (0, -1), (0, 1), (1, 0)],
+            Self::Eight => &[
+                (-1, -1),
+                (-1, 0),
+                (-1, 1),
+                (0, -1),
+                (0, 1),
+                (1, -1),
+                (1, 0),
+                (1, 1),
+            ],
+        }
+    }
+}
+
+#[derive(Debug)]
+struct UnionFind {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+}
+
+impl UnionFind {
+    fn new(size: usize) -> Self {
+        Self {
+            parent: (0..size).collect(),
+            rank: vec![0; size],
+        }
+    }
+
+    fn find(&mut self, mut a: usize) -> usize {
+        while self.parent[a] != a {
+            self.parent[a] = self.parent[self.parent[a]];
+            a = self.parent[a];
+        }
+        a
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (a, b) = (self.find(a), self.find(b));
+        if a == b {
+            return;
+        }
+
+        match self.rank[a].cmp(&self.rank[b]) {
+            std::cmp::Ordering::Less => self.parent[a] = b,
+            std::cmp::Ordering::Greater => self.parent[b] = a,
+            std::cmp::Ordering::Equal => {
+                self.parent[b] = a;
+                self.rank[a] += 1;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    pub label: usize,
+    pub size: usize,
+    /// `(min_y, min_x, max_y, max_x)`, inclusive.
+    pub bounding_box: (usize, usize, usize, usize),
+    /// Number of cell edges bordering something that isn't paper, room edges included.
+    pub perimeter: usize,
+}
+
+/// Paper cells grouped into connected components.
+///
+/// Labels are numbered from 0 in the order their first cell shows up when
+/// scanning row by row.
+#[derive(Debug, Clone)]
+pub struct Components {
+    labels: Vec<Vec<Option<usize>>>,
+    components: Vec<Component>,
+}
+
+impl Components {
+    pub fn from_room(room: &Room, neighborhood: Neighborhood) -> Result<Self> {
+        let size_y = room.get_size_y();
+        let size_x = room.get_size_x()?;
+        let is_paper = |y: usize, x: usize| room.get_item_at(y, x) == Some(Item::Paper);
+
+        let mut uf = UnionFind::new(size_y * size_x);
+        for y in 0..size_y {
+            for x in 0..size_x {
+                if !is_paper(y, x) {
+                    continue;
+                }
+
+                for (ny, nx) in offset_cells(y, x, neighborhood) {
+                    if is_paper(ny, nx) {
+                        uf.union(y * size_x + x, ny * size_x + nx);
+                    }
+                }
+            }
+        }
+
+        // label of each union-find root, handed out on first sight
+        let mut root_labels: Vec<Option<usize>> = vec![None; size_y * size_x];
+        let mut labels = vec![vec![None; size_x]; size_y];
+        let mut components: Vec<Component> = vec![];
+
+        for (y, row) in labels.iter_mut().enumerate() {
+            for (x, cell) in row.iter_mut().enumerate() {
+                if !is_paper(y, x) {
+                    continue;
+                }
+
+                let root = uf.find(y * size_x + x);
+                let label = match root_labels[root] {
+                    Some(label) => label,
+                    None => {
+                        root_labels[root] = Some(components.len());
+                        components.push(Component {
+                            label: components.len(),
+                            size: 0,
+                            bounding_box: (y, x, y, x),
+                            perimeter: 0,
+                        });
+                        components.len() - 1
+                    }
+                };
+                *cell = Some(label);
+
+                let component = &mut components[label];
+                let (min_y, min_x, max_y, max_x) = component.bounding_box;
+                component.size += 1;
+                component.bounding_box = (min_y.min(y), min_x.min(x), max_y.max(y), max_x.max(x));
+                component.perimeter += Neighborhood::Four
+                    .offsets()
+                    .iter()
+                    .filter(|(dy, dx)| {
+                        let (ny, nx) = (y.checked_add_signed(*dy), x.checked_add_signed(*dx));
+                        !matches!((ny, nx), (Some(ny), Some(nx)) if is_paper(ny, nx))
+                    })
+                    .count();
+            }
+        }
+
+        Ok(Self { labels, components })
+    }
+
+    /// Component label of every cell, `None` where there is no paper.
+    pub fn labels(&self) -> &[Vec<Option<usize>>] {
+        &self.labels
+    }
+
+    pub fn label_at(&self, y: usize, x: usize) -> Option<usize> {
+        *self.labels.get(y)?.get(x)?
+    }
+
+    pub fn components(&self) -> &[Component] {
+        &self.components
+    }
+}
+
+fn offset_cells(
+    y: usize,
+    x: usize,
+    neighborhood: Neighborhood,
+) -> impl Iterator<Item = (usize, usize)> {
+    neighborhood
+        .offsets()
+        .iter()
+        .filter_map(move |(dy, dx)| Some((y.checked_add_signed(*dy)?, x.checked_add_signed(*dx)?)))
+}
+
+/// What the peeling process did to one of the room's original components.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComponentHistory {
+    pub label: usize,
+    /// How many separate pieces are left of it before each wave, starting with 1.
+    pub pieces: Vec<usize>,
+    /// Wave that took away its last roll, if any.
+    pub erased_at: Option<usize>,
+}
+
+/// Runs part two's removal to completion and tracks how each original component falls apart.
+pub fn peel_history(mut room: Room, neighborhood: Neighborhood) -> Result<Vec<ComponentHistory>> {
+    let original = Components::from_room(&room, neighborhood)?;
+    let mut history: Vec<ComponentHistory> = original
+        .components()
+        .iter()
+        .map(|c| ComponentHistory {
+            label: c.label,
+            pieces: vec![1],
+            erased_at: None,
+        })
+        .collect();
+
+    let mut wave = 0;
+    while !room.remove_accessible()?.is_empty() {
+        let current = Components::from_room(&room, neighborhood)?;
+        let mut pieces = vec![HashSet::new(); history.len()];
+
+        for (y, row) in original.labels().iter().enumerate() {
+            for (x, label) in row.iter().enumerate() {
+                if let (Some(label), Some(now)) = (label, current.label_at(y, x)) {
+                    pieces[*label].insert(now);
+                }
+            }
+        }
+
+        for (entry, pieces) in history.iter_mut().zip(pieces) {
+            if entry.erased_at.is_some() {
+                continue;
+            }
+            if pieces.is_empty() {
+                entry.erased_at = Some(wave);
+            }
+            entry.pieces.push(pieces.len());
+        }
+        wave += 1;
+    }
+
+    Ok(history)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn room(input: &str) -> Result<Room> {
+        Room::from_lines(input.lines().map(|l| l.to_string()))
+    }
+
+    #[test]
+    fn label_components() -> Result<()> {
+        let room = room(
+            "\
+@@..@
+@...@
+.@@..
+@@@.@",
+        )?;
+
+        let four = Components::from_room(&room, Neighborhood::Four)?;
+        assert_eq!(four.components().len(), 4);
+        assert_eq!(
+            four.components()[0],
+            Component {
+                label: 0,
+                size: 3,
+                bounding_box: (0, 0, 1, 1),
+                perimeter: 8,
+            }
+        );
+        assert_eq!(four.label_at(2, 2), Some(2));
+        assert_eq!(four.label_at(3, 0), Some(2));
+        assert_eq!(four.label_at(0, 2), None);
+
+        let eight = Components::from_room(&room, Neighborhood::Eight)?;
+        assert_eq!(eight.components().len(), 3);
+        assert_eq!(eight.components()[0].size, 8);
+        assert_eq!(eight.components()[0].bounding_box, (0, 0, 3, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn example_peeling() -> Result<()> {
+        let room = Room::from_lines(
+            include_str!("testinput.txt")
+                .lines()
+                .map(|line| line.to_string()),
+        )?;
+
+        let components = Components::from_room(&room, Neighborhood::Eight)?;
+        assert_eq!(
+            components
+                .components()
+                .iter()
+                .map(|c| c.size)
+                .sum::<usize>(),
+            71
+        );
+
+        // the whole example is one blob, split in two before the third wave only
+        let history = peel_history(room, Neighborhood::Eight)?;
+        assert_eq!(
+            history,
+            vec![ComponentHistory {
+                label: 0,
+                pieces: vec![1, 1, 2, 1, 1, 1, 1, 1, 1, 1],
+                erased_at: None,
+            }]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,316 @@
+use std::fmt;
+
+use aoc_core::{Grid, InputError, Solution};
+use eyre::{Result, eyre};
+
+pub mod bitroom;
+pub mod cell;
+pub mod components;
+pub mod path;
+pub mod render;
+
+use cell::{CellKind, CellPalette};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Item {
+    Floor,
+    Paper,
+    /// Extra kind defined by a `CellPalette`, indexed in the order it was added.
+    Other(u8),
+}
+
+impl TryFrom<char> for Item {
+    type Error = eyre::ErrReport;
+    fn try_from(value: char) -> Result<Self> {
+        match value {
+            '.' => Ok(Self::Floor),
+            '@' => Ok(Self::Paper),
+            _ => Err(InputError::unknown("grid char", value).into()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Room {
+    grid: Grid<Item>,
+    palette: CellPalette,
+}
+
+impl Room {
+    pub fn from_lines<I>(iter: I) -> eyre::Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        Self::from_lines_with_palette(iter, &CellPalette::default())
+    }
+
+    pub fn from_lines_with_palette<I>(iter: I, palette: &CellPalette) -> eyre::Result<Self>
+    where
+        I: IntoIterator<Item = String>,
+    {
+        let rows = iter
+            .into_iter()
+            .map(|line| line.chars().map(|c| palette.item(c)).collect())
+            .collect::<Result<Vec<Vec<_>>, _>>()?;
+        let grid = Grid::from_rows(rows)?;
+
+        Ok(Self {
+            grid,
+            palette: palette.clone(),
+        })
+    }
+
+    pub fn palette(&self) -> &CellPalette {
+        &self.palette
+    }
+
+    pub fn neighbors(&self, y: usize, x: usize) -> Result<Vec<Item>> {
+        let size_y = self.get_size_y();
+        let size_x = self.get_size_x()?;
+
+        if x >= size_x || y >= size_y {
+            return Err(eyre!("invalid param(s): {x} {y}"));
+        }
+
+        Ok(self
+            .grid
+            .neighbors(y, x)
+            .map(|pos| self.grid[pos])
+            .collect())
+    }
+
+    /// Number of neighbours whose kind counts as a neighbour, for the default palette that's just paper.
+    pub fn neighbor_paper_count(&self, y: usize, x: usize) -> Result<usize> {
+        let mut count = 0;
+        for item in self.neighbors(y, x)? {
+            if self.kind(item)?.counts_as_neighbor {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    fn kind(&self, item: Item) -> Result<&CellKind> {
+        self.palette
+            .kind(item)
+            .ok_or(eyre!("item not in the palette: {:?}", item))
+    }
+
+    pub fn get_size_y(&self) -> usize {
+        self.grid.height()
+    }
+
+    pub fn get_size_x(&self) -> Result<usize> {
+        if self.grid.height() == 0 {
+            return Err(eyre!("board empty"));
+        }
+        Ok(self.grid.width())
+    }
+
+    pub fn get_item_at(&self, y: usize, x: usize) -> Option<Item> {
+        self.grid.get(y, x).copied()
+    }
+
+    pub fn set_item_at(&mut self, y: usize, x: usize, item: Item) {
+        self.grid[(y, x)] = item;
+    }
+
+    pub fn accessible_rolls(&self) -> Result<Vec<(usize, usize)>> {
+        let mut out = vec![];
+        for i in 0..self.get_size_y() {
+            for j in 0..self.get_size_x()? {
+                if self.kind(self.grid[(i, j)])?.removable && self.neighbor_paper_count(i, j)? < 4 {
+                    out.push((i, j));
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Removes all currently accessible rolls at once and returns where they were.
+    pub fn remove_accessible(&mut self) -> Result<Vec<(usize, usize)>> {
+        let removed = self.accessible_rolls()?;
+        for &(y, x) in &removed {
+            self.set_item_at(y, x, Item::Floor);
+        }
+        Ok(removed)
+    }
+}
+
+impl fmt::Display for Room {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in self.grid.rows() {
+            for item in row {
+                write!(f, "{}", self.palette.symbol(*item).ok_or(fmt::Error)?)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn part_one(&self, input: &str) -> Result<String> {
+        let room = Room::from_lines(input.lines().map(|l| l.to_string()))?;
+        Ok(room.accessible_rolls()?.len().to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Result<String> {
+        let mut room = Room::from_lines(input.lines().map(|l| l.to_string()))?;
+        let mut removed = 0;
+        loop {
+            let wave = room.remove_accessible()?.len();
+            if wave == 0 {
+                break;
+            }
+            removed += wave;
+        }
+        Ok(removed.to_string())
+    }
+}
+
+/// Lines of the puzzle input and the recorded answer to `part`, for the tests.
+#[cfg(test)]
+fn recorded_input(part: u8) -> Result<(Vec<String>, String)> {
+    let src = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+    let (input, expected) = aoc_core::answers::recorded(src, 4, "input", part)?;
+    Ok((
+        input.lines().map(|line| line.to_string()).collect(),
+        expected,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_one() -> Result<()> {
+        let room = Room::from_lines(
+            include_str!("testinput.txt")
+                .lines()
+                .map(|line| line.to_string()),
+        )?;
+
+        let mut forklift_accessible = 0;
+        for i in 0..room.get_size_y() {
+            for j in 0..room.get_size_x()? {
+                if room.get_item_at(i, j).unwrap() == Item::Paper
+                    && room.neighbor_paper_count(i, j)? < 4
+                {
+                    forklift_accessible += 1;
+                }
+            }
+        }
+        assert_eq!(forklift_accessible, 13);
+        Ok(())
+    }
+
+    #[test]
+    fn example_two() -> Result<()> {
+        let mut room = Room::from_lines(
+            include_str!("testinput.txt")
+                .lines()
+                .map(|line| line.to_string()),
+        )?;
+
+        let size_y = room.get_size_y();
+        let size_x = room.get_size_x()?;
+
+        let mut rolls_counter = 0;
+
+        loop {
+            let mut cells_to_update = vec![];
+            let mut forklift_accessible = 0;
+
+            for i in 0..size_y {
+                for j in 0..size_x {
+                    let neighbor_paper_count = room.neighbor_paper_count(i, j)?;
+                    let item = room.get_item_at(i, j).unwrap();
+
+                    if item == Item::Paper && neighbor_paper_count < 4 {
+                        forklift_accessible += 1;
+                        cells_to_update.push((i, j));
+                    }
+                }
+            }
+
+            for (y, x) in cells_to_update {
+                room.set_item_at(y, x, Item::Floor);
+            }
+
+            if forklift_accessible == 0 {
+                break;
+            }
+            rolls_counter += forklift_accessible;
+        }
+
+        assert_eq!(rolls_counter, 43);
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let (lines, expected) = recorded_input(1)?;
+        let room = Room::from_lines(lines)?;
+
+        let mut forklift_accessible = 0;
+        for i in 0..room.get_size_y() {
+            for j in 0..room.get_size_x()? {
+                if room.get_item_at(i, j).unwrap() == Item::Paper
+                    && room.neighbor_paper_count(i, j)? < 4
+                {
+                    forklift_accessible += 1;
+                }
+            }
+        }
+        assert_eq!(forklift_accessible.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_solution() -> Result<()> {
+        let (lines, expected) = recorded_input(2)?;
+        let mut room = Room::from_lines(lines)?;
+
+        let size_y = room.get_size_y();
+        let size_x = room.get_size_x()?;
+
+        let mut rolls_counter = 0;
+
+        loop {
+            let mut cells_to_update = vec![];
+            let mut forklift_accessible = 0;
+
+            for i in 0..size_y {
+                for j in 0..size_x {
+                    let neighbor_paper_count = room.neighbor_paper_count(i, j)?;
+                    let item = room.get_item_at(i, j).unwrap();
+
+                    if item == Item::Paper && neighbor_paper_count < 4 {
+                        forklift_accessible += 1;
+                        cells_to_update.push((i, j));
+                    }
+                }
+            }
+
+            for (y, x) in cells_to_update {
+                room.set_item_at(y, x, Item::Floor);
+            }
+
+            if forklift_accessible == 0 {
+                break;
+            }
+            rolls_counter += forklift_accessible;
+        }
+
+        assert_eq!(rolls_counter.to_string(), expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,219 @@
+use std::collections::VecDeque;
+
+use eyre::{Result, eyre};
+
+use crate::{Item, Room};
+
+type Pos = (usize, usize);
+
+/// Positions of all cells named `dock` in the room's palette.
+pub fn docks(room: &Room) -> Vec<Pos> {
+    let size_x = room.get_size_x().unwrap_or(0);
+
+    (0..room.get_size_y())
+        .flat_map(|y| (0..size_x).map(move |x| (y, x)))
+        .filter(|&(y, x)| {
+            room.get_item_at(y, x)
+                .and_then(|item| room.palette().kind(item))
+                .is_some_and(|kind| kind.name == "dock")
+        })
+        .collect()
+}
+
+/// Where a forklift starting from any of the docks can drive.
+///
+/// The forklift moves up, down, left and right over floor cells only. A roll
+/// is reachable when the forklift can stop right next to it.
+#[derive(Debug)]
+pub struct Reachability<'a> {
+    room: &'a Room,
+    // previous cell on a shortest path, docks point at themselves
+    parents: Vec<Vec<Option<Pos>>>,
+}
+
+impl<'a> Reachability<'a> {
+    pub fn from_docks(room: &'a Room, docks: &[Pos]) -> Result<Self> {
+        let size_y = room.get_size_y();
+        let size_x = room.get_size_x()?;
+        let mut parents = vec![vec![None; size_x]; size_y];
+        let mut queue = VecDeque::new();
+
+        for &(y, x) in docks {
+            let item = room
+                .get_item_at(y, x)
+                .ok_or(eyre!("dock out of bounds: {y} {x}"))?;
+            // the forklift starts out on the dock, so it has to be a cell it can drive on
+            let kind = room.palette().kind(item);
+            if item != Item::Floor && kind.is_none_or(|kind| kind.name != "dock") {
+                let name = kind.map_or("unknown cell", |kind| kind.name.as_str());
+                return Err(eyre!("dock placed on {name}: {y} {x}"));
+            }
+
+            if parents[y][x].is_none() {
+                parents[y][x] = Some((y, x));
+                queue.push_back((y, x));
+            }
+        }
+
+        while let Some(pos) = queue.pop_front() {
+            for (y, x) in orthogonal(pos, size_y, size_x) {
+                if parents[y][x].is_none() && room.get_item_at(y, x) == Some(Item::Floor) {
+                    parents[y][x] = Some(pos);
+                    queue.push_back((y, x));
+                }
+            }
+        }
+
+        Ok(Self { room, parents })
+    }
+
+    pub fn is_reachable(&self, y: usize, x: usize) -> bool {
+        self.parents
+            .get(y)
+            .and_then(|row| row.get(x))
+            .is_some_and(|p| p.is_some())
+    }
+
+    /// Every cell the forklift can drive to, docks included.
+    pub fn reachable_region(&self) -> Vec<Pos> {
+        self.parents
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, p)| p.is_some())
+                    .map(move |(x, _)| (y, x))
+            })
+            .collect()
+    }
+
+    /// Shortest drive from a dock to the given cell, both ends included.
+    pub fn path_to(&self, y: usize, x: usize) -> Option<Vec<Pos>> {
+        if !self.is_reachable(y, x) {
+            return None;
+        }
+
+        let mut path = vec![(y, x)];
+        let mut pos = (y, x);
+        while let Some(parent) = self.parents[pos.0][pos.1].filter(|p| *p != pos) {
+            path.push(parent);
+            pos = parent;
+        }
+
+        path.reverse();
+        Some(path)
+    }
+
+    /// Shortest drive from a dock to a cell next to the roll, with the roll itself as the last step.
+    pub fn path_to_roll(&self, y: usize, x: usize) -> Option<Vec<Pos>> {
+        let size_y = self.parents.len();
+        let size_x = self.parents.first()?.len();
+
+        let mut path = orthogonal((y, x), size_y, size_x)
+            .filter_map(|(ny, nx)| self.path_to(ny, nx))
+            .min_by_key(|path| path.len())?;
+
+        path.push((y, x));
+        Some(path)
+    }
+
+    /// Accessible rolls the forklift can get to, each with its shortest path.
+    pub fn accessible_paths(&self) -> Result<Vec<(Pos, Vec<Pos>)>> {
+        Ok(self
+            .room
+            .accessible_rolls()?
+            .into_iter()
+            .filter_map(|(y, x)| Some(((y, x), self.path_to_roll(y, x)?)))
+            .collect())
+    }
+
+    /// Rolls with few enough neighbours to be taken, but walled off from every dock.
+    pub fn unreachable_accessible(&self) -> Result<Vec<Pos>> {
+        Ok(self
+            .room
+            .accessible_rolls()?
+            .into_iter()
+            .filter(|&(y, x)| self.path_to_roll(y, x).is_none())
+            .collect())
+    }
+}
+
+fn orthogonal((y, x): Pos, size_y: usize, size_x: usize) -> impl Iterator<Item = Pos> {
+    [
+        y.checked_sub(1).map(|y| (y, x)),
+        (y + 1 < size_y).then_some((y + 1, x)),
+        x.checked_sub(1).map(|x| (y, x)),
+        (x + 1 < size_x).then_some((y, x + 1)),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::cell::CellPalette;
+
+    fn room(input: &str, palette: &CellPalette) -> Result<Room> {
+        Room::from_lines_with_palette(input.lines().map(|l| l.to_string()), palette)
+    }
+
+    #[test]
+    fn paths_from_dock() -> Result<()> {
+        let room = room(
+            "\
+#######
+D..@..#
+#.###.#
+#.#@@.#
+#######",
+            &CellPalette::warehouse(),
+        )?;
+
+        let docks = docks(&room);
+        assert_eq!(docks, vec![(1, 0)]);
+
+        let reach = Reachability::from_docks(&room, &docks)?;
+        assert_eq!(reach.reachable_region().len(), 5);
+        assert!(!reach.is_reachable(1, 4));
+
+        assert_eq!(
+            reach.path_to_roll(1, 3),
+            Some(vec![(1, 0), (1, 1), (1, 2), (1, 3)])
+        );
+        assert_eq!(reach.path_to_roll(3, 4), None);
+        assert_eq!(reach.unreachable_accessible()?, vec![(3, 3), (3, 4)]);
+        assert_eq!(reach.accessible_paths()?.len(), 1);
+
+        // walls, pillars and goods can't hold a dock either
+        for pos in [(0, 0), (2, 2), (3, 3)] {
+            assert!(Reachability::from_docks(&room, &[pos]).is_err());
+        }
+        assert!(Reachability::from_docks(&room, &[(1, 1)]).is_ok());
+        Ok(())
+    }
+
+    #[test]
+    fn example_from_corner() -> Result<()> {
+        let room = Room::from_lines(
+            include_str!("testinput.txt")
+                .lines()
+                .map(|line| line.to_string()),
+        )?;
+
+        let reach = Reachability::from_docks(&room, &[(0, 0)])?;
+        assert_eq!(reach.reachable_region(), vec![(0, 0), (0, 1)]);
+
+        let paths = reach.accessible_paths()?;
+        assert_eq!(
+            paths.iter().map(|(pos, _)| *pos).collect::<Vec<_>>(),
+            vec![(0, 2), (1, 0)]
+        );
+        assert_eq!(reach.unreachable_accessible()?.len(), 11);
+
+        assert!(Reachability::from_docks(&room, &[(0, 2)]).is_err());
+        assert!(Reachability::from_docks(&room, &[(10, 0)]).is_err());
+        Ok(())
+    }
+}
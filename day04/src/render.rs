@@ -0,0 +1,302 @@
+use std::{
+    borrow::Cow,
+    fmt, fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use eyre::{Result, eyre};
+
+use crate::{Item, Room};
+
+#[derive(Debug, Clone)]
+pub struct Frame {
+    room: Room,
+    removed: Vec<(usize, usize)>,
+}
+
+impl Frame {
+    pub fn removed(&self) -> &[(usize, usize)] {
+        &self.removed
+    }
+
+    /// Every cell of the frame row by row, with this wave's removals marked.
+    fn cells(&self) -> Vec<Vec<Cell>> {
+        let size_x = self.room.get_size_x().unwrap_or(0);
+        let mut cells = (0..self.room.get_size_y())
+            .map(|y| {
+                (0..size_x)
+                    .map(|x| match self.room.get_item_at(y, x) {
+                        Some(Item::Paper) => Cell::Paper,
+                        Some(Item::Other(_)) => Cell::Other,
+                        _ => Cell::Floor,
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+
+        for &(y, x) in &self.removed {
+            cells[y][x] = Cell::Removed;
+        }
+        cells
+    }
+}
+
+impl fmt::Display for Frame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.cells().into_iter().enumerate() {
+            for (x, cell) in row.into_iter().enumerate() {
+                let c = match (cell, self.room.get_item_at(y, x)) {
+                    (Cell::Removed, _) => 'x',
+                    (_, Some(item)) => self.room.palette().symbol(item).ok_or(fmt::Error)?,
+                    (_, None) => ' ',
+                };
+                write!(f, "{}", c)?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Cell {
+    Floor,
+    Paper,
+    Removed,
+    Other,
+}
+
+/// Runs the removal process to completion and returns one frame per wave.
+///
+/// Each frame shows the room as it was before the wave, with the rolls the
+/// wave takes away marked as removed. The last frame is the stable room.
+pub fn waves(mut room: Room) -> Result<Vec<Frame>> {
+    let mut frames = vec![];
+
+    loop {
+        let removed = room.accessible_rolls()?;
+        let done = removed.is_empty();
+
+        frames.push(Frame {
+            room: room.clone(),
+            removed,
+        });
+
+        if done {
+            break;
+        }
+        room.remove_accessible()?;
+    }
+
+    Ok(frames)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+#[derive(Debug, Clone, Copy)]
+pub struct Palette {
+    pub floor: Rgb,
+    pub paper: Rgb,
+    pub removed: Rgb,
+    /// Used for every kind besides floor and paper, e.g. walls.
+    pub other: Rgb,
+}
+
+impl Default for Palette {
+    fn default() -> Self {
+        Self {
+            floor: Rgb(32, 32, 32),
+            paper: Rgb(230, 220, 190),
+            removed: Rgb(220, 50, 50),
+            other: Rgb(90, 110, 140),
+        }
+    }
+}
+
+impl Palette {
+    fn color(&self, cell: Cell) -> Rgb {
+        match cell {
+            Cell::Floor => self.floor,
+            Cell::Paper => self.paper,
+            Cell::Removed => self.removed,
+            Cell::Other => self.other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Renderer {
+    scale: usize,
+    palette: Palette,
+}
+
+impl Default for Renderer {
+    fn default() -> Self {
+        Self {
+            scale: 4,
+            palette: Palette::default(),
+        }
+    }
+}
+
+impl Renderer {
+    pub fn new(scale: usize, palette: Palette) -> Result<Self> {
+        if scale == 0 {
+            return Err(eyre!("scale must be at least 1"));
+        }
+        Ok(Self { scale, palette })
+    }
+
+    fn dimensions(&self, frame: &Frame) -> Result<(usize, usize)> {
+        Ok((
+            frame.room.get_size_x()? * self.scale,
+            frame.room.get_size_y() * self.scale,
+        ))
+    }
+
+    /// Cells of the frame scaled up to one entry per pixel, row by row.
+    fn pixels(&self, frame: &Frame) -> Result<impl Iterator<Item = Cell>> {
+        let (width, height) = self.dimensions(frame)?;
+        let cells = frame.cells();
+
+        let pixels = (0..height)
+            .flat_map(|py| (0..width).map(move |px| (py, px)))
+            .map(|(py, px)| cells[py / self.scale][px / self.scale])
+            .collect::<Vec<_>>();
+        Ok(pixels.into_iter())
+    }
+
+    pub fn write_ppm<W: Write>(&self, frame: &Frame, mut out: W) -> Result<()> {
+        let (width, height) = self.dimensions(frame)?;
+
+        write!(out, "P6\n{} {}\n255\n", width, height)?;
+
+        let data: Vec<u8> = self
+            .pixels(frame)?
+            .flat_map(|cell| {
+                let Rgb(r, g, b) = self.palette.color(cell);
+                [r, g, b]
+            })
+            .collect();
+        out.write_all(&data)?;
+
+        Ok(())
+    }
+
+    /// Writes `frame_0000.ppm`, `frame_0001.ppm`, ... into `dir`, creating it if needed.
+    pub fn write_ppm_sequence(&self, frames: &[Frame], dir: &Path) -> Result<Vec<PathBuf>> {
+        fs::create_dir_all(dir)?;
+
+        frames
+            .iter()
+            .enumerate()
+            .map(|(idx, frame)| {
+                let path = dir.join(format!("frame_{:04}.ppm", idx));
+                self.write_ppm(frame, fs::File::create(&path)?)?;
+                Ok(path)
+            })
+            .collect()
+    }
+
+    /// Writes all frames as a looping animated GIF, `delay` is in hundredths of a second.
+    pub fn write_gif<W: Write>(&self, frames: &[Frame], out: W, delay: u16) -> Result<()> {
+        let first = frames.first().ok_or(eyre!("no frames to render"))?;
+        let (width, height) = self.dimensions(first)?;
+        let width = u16::try_from(width).map_err(|_| eyre!("gif too wide: {}", width))?;
+        let height = u16::try_from(height).map_err(|_| eyre!("gif too tall: {}", height))?;
+
+        let global_palette: Vec<u8> = [Cell::Floor, Cell::Paper, Cell::Removed, Cell::Other]
+            .into_iter()
+            .flat_map(|cell| {
+                let Rgb(r, g, b) = self.palette.color(cell);
+                [r, g, b]
+            })
+            .collect();
+
+        let mut encoder = gif::Encoder::new(out, width, height, &global_palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+
+        for frame in frames {
+            let buffer: Vec<u8> = self
+                .pixels(frame)?
+                .map(|cell| match cell {
+                    Cell::Floor => 0,
+                    Cell::Paper => 1,
+                    Cell::Removed => 2,
+                    Cell::Other => 3,
+                })
+                .collect();
+
+            encoder.write_frame(&gif::Frame {
+                width,
+                height,
+                delay,
+                buffer: Cow::Owned(buffer),
+                ..Default::default()
+            })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example_room() -> Result<Room> {
+        Room::from_lines(
+            include_str!("testinput.txt")
+                .lines()
+                .map(|line| line.to_string()),
+        )
+    }
+
+    #[test]
+    fn example_waves() -> Result<()> {
+        let frames = waves(example_room()?)?;
+
+        let removed = frames.iter().map(|f| f.removed().len()).collect::<Vec<_>>();
+        assert_eq!(removed, vec![13, 12, 7, 5, 2, 1, 1, 1, 1, 0]);
+        assert_eq!(removed.iter().sum::<usize>(), 43);
+
+        assert_eq!(
+            frames[0].to_string().lines().take(3).collect::<Vec<_>>(),
+            vec!["..xx.xx@x.", "x@@.@.@.@@", "@@@@@.x.@@"]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn ppm_frame() -> Result<()> {
+        let frames = waves(example_room()?)?;
+        let renderer = Renderer::new(2, Palette::default())?;
+
+        let mut out = vec![];
+        renderer.write_ppm(&frames[0], &mut out)?;
+
+        let header = b"P6\n20 20\n255\n";
+        assert!(out.starts_with(header));
+        assert_eq!(out.len(), header.len() + 20 * 20 * 3);
+
+        // top left cell is floor, the one at x=2 was just removed
+        let Rgb(r, g, b) = Palette::default().removed;
+        assert_eq!(&out[header.len() + 4 * 3..header.len() + 5 * 3], &[r, g, b]);
+        Ok(())
+    }
+
+    #[test]
+    fn gif_animation() -> Result<()> {
+        let frames = waves(example_room()?)?;
+
+        let mut out = vec![];
+        Renderer::default().write_gif(&frames, &mut out, 10)?;
+
+        assert!(out.starts_with(b"GIF89a"));
+        assert_eq!(u16::from_le_bytes([out[6], out[7]]), 40);
+        assert_eq!(out.last(), Some(&0x3b));
+        Ok(())
+    }
+}
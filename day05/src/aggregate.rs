@@ -0,0 +1,303 @@
+use aoc_core::Interval;
+use eyre::Result;
+
+use crate::{Database, Id, interval_set::IntervalSet, span::Span};
+
+/// Read-only snapshot of the merged ranges for aggregate queries.
+///
+/// Touching ranges get joined, so the id right before a range and right after
+/// it are always spoiled. Prefix sums give fresh counts and a sparse table over
+/// the gaps between ranges gives the longest spoiled stretch, both in `O(log n)`.
+#[derive(Debug, Clone)]
+pub struct FreshnessIndex {
+    from: Vec<u64>,
+    to: Vec<u64>,
+    // fresh ids in all ranges before index i
+    prefix: Vec<u128>,
+    // sparse[k][i]: gap with the most ids among gaps i..i + 2^k, see `gap`
+    sparse: Vec<Vec<usize>>,
+}
+
+impl FreshnessIndex {
+    pub fn new(set: &IntervalSet<Id>) -> Self {
+        let mut from: Vec<u64> = vec![];
+        let mut to: Vec<u64> = vec![];
+
+        for range in set.iter() {
+            match to.last_mut() {
+                Some(last) if last.checked_add(1) == Some(range.from.0) => *last = range.to.0,
+                _ => {
+                    from.push(range.from.0);
+                    to.push(range.to.0);
+                }
+            }
+        }
+
+        let mut prefix = vec![0];
+        for (f, t) in from.iter().zip(&to) {
+            prefix.push(prefix.last().unwrap_or(&0) + (t - f) as u128 + 1);
+        }
+
+        let mut index = Self {
+            from,
+            to,
+            prefix,
+            sparse: vec![],
+        };
+        index.build_sparse();
+        index
+    }
+
+    fn build_sparse(&mut self) {
+        let gaps = self.from.len() + 1;
+        let mut level: Vec<usize> = (0..gaps).collect();
+        let mut width = 1;
+
+        while !level.is_empty() {
+            let next = (0..level.len().saturating_sub(width))
+                .map(|i| self.longer_gap(level[i], level[i + width]))
+                .collect();
+            self.sparse.push(level);
+            level = next;
+            width *= 2;
+        }
+    }
+
+    fn longer_gap(&self, a: usize, b: usize) -> usize {
+        let len = |g| self.gap(g).map_or(0, |r| r.len());
+        if len(b) > len(a) { b } else { a }
+    }
+
+    /// Spoiled ids before range `i`, gap 0 is before the first range and gap n after the last.
+    fn gap(&self, i: usize) -> Option<Interval<Id>> {
+        let start = match i {
+            0 => 0,
+            _ => self.to[i - 1].checked_add(1)?,
+        };
+        let end = match self.from.get(i) {
+            Some(from) => from.checked_sub(1)?,
+            None => u64::MAX,
+        };
+
+        (start <= end).then_some(Interval {
+            from: Id(start),
+            to: Id(end),
+        })
+    }
+
+    fn longest_gap_between(&self, lo: usize, hi: usize) -> usize {
+        let level = (hi - lo + 1).ilog2() as usize;
+        self.longer_gap(
+            self.sparse[level][lo],
+            self.sparse[level][hi + 1 - (1 << level)],
+        )
+    }
+
+    // fresh ids <= x
+    fn fresh_up_to(&self, x: u64) -> u128 {
+        let i = self.from.partition_point(|f| *f <= x);
+        if i == 0 {
+            return 0;
+        }
+        self.prefix[i - 1] + (self.to[i - 1].min(x) - self.from[i - 1]) as u128 + 1
+    }
+
+    /// Number of fresh ids in `span`.
+    ///
+    /// A stepped span walks the merged ranges inside its bounds instead of
+    /// using the prefix sums, so it costs `O(log n + k)` for `k` such ranges.
+    pub fn count_fresh(&self, span: impl Into<Span>) -> u128 {
+        let span = span.into();
+        if span.is_empty() {
+            return 0;
+        }
+
+        if let Ok(Some(range)) = span.to_interval() {
+            let below = match range.from.0 {
+                0 => 0,
+                from => self.fresh_up_to(from - 1),
+            };
+            return self.fresh_up_to(range.to.0) - below;
+        }
+
+        let lo = self.to.partition_point(|t| *t < span.ids.from.0);
+        let hi = self.from.partition_point(|f| *f <= span.ids.to.0);
+        (lo..hi)
+            .map(|i| {
+                span.clip(Interval::new(Id(self.from[i]), Id(self.to[i])))
+                    .len()
+            })
+            .sum()
+    }
+
+    /// Smallest fresh id that is `>= id`.
+    pub fn next_fresh(&self, id: Id) -> Option<Id> {
+        let i = self.to.partition_point(|t| *t < id.0);
+        Some(Id(self.from.get(i)?.max(&id.0).to_owned()))
+    }
+
+    /// Largest spoiled id that is `<= id`.
+    pub fn prev_spoiled(&self, id: Id) -> Option<Id> {
+        let i = self.from.partition_point(|f| *f <= id.0);
+        match i.checked_sub(1) {
+            Some(i) if self.to[i] >= id.0 => self.from[i].checked_sub(1).map(Id),
+            _ => Some(id),
+        }
+    }
+
+    /// Longest run of spoiled ids inside `window`, the first one on ties.
+    ///
+    /// Fails for stepped windows, a run of ids has no gaps in it.
+    pub fn longest_spoiled_gap(&self, window: impl Into<Span>) -> Result<Option<Interval<Id>>> {
+        let Some(window) = window.into().to_interval()? else {
+            return Ok(None);
+        };
+
+        let clip = |g: usize| {
+            let gap = self.gap(g)?;
+            let clipped = Interval {
+                from: gap.from.max(window.from),
+                to: gap.to.min(window.to),
+            };
+            (clipped.from <= clipped.to).then_some(clipped)
+        };
+
+        // gaps lo..=hi touch the window, only the outer two can stick out of it
+        let lo = self.from.partition_point(|f| *f <= window.from.0);
+        let hi = self.from.partition_point(|f| *f <= window.to.0);
+
+        let mut candidates = vec![clip(lo)];
+        if hi > lo + 1 {
+            candidates.push(self.gap(self.longest_gap_between(lo + 1, hi - 1)));
+        }
+        if hi > lo {
+            candidates.push(clip(hi));
+        }
+
+        Ok(candidates.into_iter().flatten().fold(
+            None,
+            |best: Option<Interval<Id>>, gap| match best {
+                Some(best) if best.len() >= gap.len() => Some(best),
+                _ => Some(gap),
+            },
+        ))
+    }
+}
+
+impl Database {
+    pub fn freshness_index(&self) -> FreshnessIndex {
+        FreshnessIndex::new(self.fresh())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::recorded_input;
+
+    fn range(from: u64, to: u64) -> Interval<Id> {
+        Interval {
+            from: Id(from),
+            to: Id(to),
+        }
+    }
+
+    #[test]
+    fn small_example() -> Result<()> {
+        let set: IntervalSet<Id> = [range(3, 5), range(6, 8), range(12, 14), range(20, 20)]
+            .into_iter()
+            .collect();
+        let index = FreshnessIndex::new(&set);
+
+        assert_eq!(index.count_fresh(range(0, 100)), 10);
+        assert_eq!(index.count_fresh(range(5, 12)), 5);
+        assert_eq!(index.next_fresh(Id(9)), Some(Id(12)));
+        assert_eq!(index.next_fresh(Id(13)), Some(Id(13)));
+        assert_eq!(index.next_fresh(Id(21)), None);
+        assert_eq!(index.prev_spoiled(Id(7)), Some(Id(2)));
+        assert_eq!(index.prev_spoiled(Id(10)), Some(Id(10)));
+        assert_eq!(index.prev_spoiled(Id(4)), Some(Id(2)));
+        assert_eq!(
+            index.longest_spoiled_gap(range(0, 19))?,
+            Some(range(15, 19))
+        );
+        assert_eq!(index.longest_spoiled_gap(range(4, 13))?, Some(range(9, 11)));
+        assert_eq!(index.longest_spoiled_gap(range(4, 8))?, None);
+        assert_eq!(
+            index.longest_spoiled_gap(range(0, u64::MAX))?,
+            Some(range(21, u64::MAX))
+        );
+
+        let full = FreshnessIndex::new(&[range(0, u64::MAX)].into_iter().collect());
+        assert_eq!(full.prev_spoiled(Id(10)), None);
+        assert_eq!(full.count_fresh(range(0, u64::MAX)), u64::MAX as u128 + 1);
+
+        // every third id from 2 on: 5, 8, 14 and 20 are fresh
+        let every_third = Span::inclusive(Id(2), Id(100)).with_step(3)?;
+        assert_eq!(index.count_fresh(every_third), 4);
+        assert_eq!(index.count_fresh(Span::exclusive(Id(5), Id(12))), 3);
+        assert!(index.longest_spoiled_gap(every_third).is_err());
+        assert_eq!(
+            index.longest_spoiled_gap(Span::exclusive(Id(8), Id(12)))?,
+            Some(range(9, 11))
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn input_ranges_match_scan() -> Result<()> {
+        let (_, db, expected) = recorded_input(2)?;
+        let index = db.freshness_index();
+        let merged = db.fresh().iter().collect::<Vec<_>>();
+        let mut rng = StdRng::seed_from_u64(37);
+
+        let max = merged.last().map_or(0, |r| r.to.0) + 1000;
+        assert_eq!(index.count_fresh(range(0, u64::MAX)).to_string(), expected);
+
+        for _ in 0..500 {
+            // pick points near range edges, uniform ones would almost never hit anything interesting
+            let near = merged[rng.random_range(0..merged.len())];
+            let a = near.from.0.saturating_add_signed(rng.random_range(-5..5));
+            let b = a.saturating_add(rng.random_range(0..max / 4));
+            let window = range(a, b.min(max));
+
+            let expected_count = merged
+                .iter()
+                .map(|r| range(r.from.0.max(a), r.to.0.min(window.to.0)).len())
+                .sum::<u128>();
+            assert_eq!(index.count_fresh(window), expected_count);
+
+            let expected_next = merged
+                .iter()
+                .find(|r| r.to.0 >= a)
+                .map(|r| Id(r.from.0.max(a)));
+            assert_eq!(index.next_fresh(Id(a)), expected_next);
+
+            let expected_prev = db
+                .fresh()
+                .complement_within(range(0, a))?
+                .iter()
+                .last()
+                .map(|r| r.to);
+            assert_eq!(index.prev_spoiled(Id(a)), expected_prev);
+
+            let spoiled = db.fresh().complement_within(window)?;
+            let expected_gap = spoiled
+                .iter()
+                .fold(None, |best: Option<Interval<Id>>, gap| match best {
+                    Some(best) if best.len() >= gap.len() => Some(best),
+                    _ => Some(gap),
+                });
+            assert_eq!(index.longest_spoiled_gap(window)?, expected_gap);
+
+            // big enough that brute force stays a few hundred ids
+            let step = rng.random_range(1..=(window.to.0 - a) / 300 + 1);
+            let stepped = Span::from(window).with_step(step)?;
+            let expected_stepped = stepped.iter().filter(|id| db.is_fresh(*id)).count();
+            assert_eq!(index.count_fresh(stepped), expected_stepped as u128);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+use aoc_core::Interval;
+use eyre::{Result, eyre};
+
+use crate::{Database, Id, span::Span};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Change {
+    Inserted(Interval<Id>),
+    Removed(Interval<Id>),
+    Cleared,
+}
+
+type Listener = Box<dyn FnMut(&Change)>;
+
+#[derive(Default)]
+pub(crate) struct Listeners(Vec<Listener>);
+
+impl fmt::Debug for Listeners {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Listeners({})", self.0.len())
+    }
+}
+
+impl Database {
+    /// Calls `listener` after every change to the fresh ranges.
+    pub fn subscribe(&mut self, listener: impl FnMut(&Change) + 'static) {
+        self.listeners.0.push(Box::new(listener));
+    }
+
+    fn notify(&mut self, change: Change) {
+        for listener in self.listeners.0.iter_mut() {
+            listener(&change);
+        }
+    }
+
+    /// Adds a freshness window, the ids it newly covers show up in `fresh_ranges` with line 0.
+    pub fn insert_range(&mut self, range: impl Into<Span>) -> Result<()> {
+        let range = contiguous(range.into())?;
+
+        // only the gaps get recorded, so inserting the same window again adds nothing
+        let gaps = self.fresh.complement_within(range)?;
+        for gap in gaps.iter() {
+            self.fresh_ranges.push(gap);
+            self.fresh_range_lines.push(0);
+        }
+        self.fresh.insert(range);
+        self.notify(Change::Inserted(range));
+        Ok(())
+    }
+
+    /// Revokes every id in `range`, splitting the ranges that stick out on either side.
+    pub fn remove_range(&mut self, range: impl Into<Span>) -> Result<()> {
+        let range = contiguous(range.into())?;
+
+        self.fresh.remove(range);
+
+        let mut ranges = vec![];
+        let mut lines = vec![];
+        for (source, line) in self.fresh_ranges.iter().zip(&self.fresh_range_lines) {
+            for piece in split_off(*source, range) {
+                ranges.push(piece);
+                lines.push(*line);
+            }
+        }
+        self.fresh_ranges = ranges;
+        self.fresh_range_lines = lines;
+
+        self.notify(Change::Removed(range));
+        Ok(())
+    }
+
+    /// Drops all fresh ranges, the ids are kept.
+    pub fn clear(&mut self) {
+        self.fresh.clear();
+        self.fresh_ranges.clear();
+        self.fresh_range_lines.clear();
+        self.notify(Change::Cleared);
+    }
+}
+
+// changes are recorded as plain ranges, so steps and empty spans are refused
+fn contiguous(span: Span) -> Result<Interval<Id>> {
+    span.to_interval()?.ok_or(eyre!("range is empty"))
+}
+
+// what's left of `source` once `cut` is taken out of it
+fn split_off(source: Interval<Id>, cut: Interval<Id>) -> impl Iterator<Item = Interval<Id>> {
+    let before = (source.from < cut.from).then(|| Interval {
+        from: source.from,
+        to: source.to.min(Id(cut.from.0 - 1)),
+    });
+    let after = (source.to > cut.to).then(|| Interval {
+        from: source.from.max(Id(cut.to.0 + 1)),
+        to: source.to,
+    });
+
+    [before, after].into_iter().flatten()
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cell::RefCell, rc::Rc};
+
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::interval_set::IntervalSet;
+
+    fn range(from: u64, to: u64) -> Interval<Id> {
+        Interval {
+            from: Id(from),
+            to: Id(to),
+        }
+    }
+
+    #[test]
+    fn insert_remove_clear() -> Result<()> {
+        let mut db = Database::from_input(include_str!("testinput.txt"))?;
+        let seen = Rc::new(RefCell::new(vec![]));
+        let log = seen.clone();
+        db.subscribe(move |change| log.borrow_mut().push(*change));
+
+        db.remove_range(range(13, 16))?;
+        assert!(db.is_fresh(Id(12)));
+        assert!(!db.is_fresh(Id(14)));
+        assert!(db.is_fresh(Id(17)));
+        assert_eq!(db.fresh_range_lines(), [1, 2, 3, 4, 4]);
+
+        db.insert_range(range(30, 40))?;
+        assert!(db.is_fresh(Id(32)));
+        assert_eq!(db.fresh_range_lines().last(), Some(&0));
+
+        // already covered ids aren't recorded twice
+        let recorded = db.fresh_ranges().len();
+        db.insert_range(range(30, 40))?;
+        db.insert_range(range(32, 35))?;
+        assert_eq!(db.fresh_ranges().len(), recorded);
+        db.insert_range(range(38, 45))?;
+        assert_eq!(db.fresh_ranges().last(), Some(&range(41, 45)));
+
+        assert!(db.insert_range(range(9, 8)).is_err());
+        assert!(db.remove_range(range(9, 8)).is_err());
+        assert!(db.insert_range(Span::from(50..=60).with_step(2)?).is_err());
+        db.remove_range(Span::exclusive(Id(2), Id(4)))?;
+        assert!(!db.is_fresh(Id(3)));
+        assert!(db.is_fresh(Id(4)));
+        assert_eq!(seen.borrow().len(), 6);
+
+        db.clear();
+        assert!(!db.is_fresh(Id(4)));
+        assert_eq!(db.ids.len(), 6);
+
+        assert_eq!(
+            *seen.borrow(),
+            vec![
+                Change::Removed(range(13, 16)),
+                Change::Inserted(range(30, 40)),
+                Change::Inserted(range(30, 40)),
+                Change::Inserted(range(32, 35)),
+                Change::Inserted(range(38, 45)),
+                Change::Removed(range(3, 3)),
+                Change::Cleared
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn matches_rebuild_after_every_change() -> Result<()> {
+        let mut rng = StdRng::seed_from_u64(38);
+        let mut db = Database::from_input(include_str!("testinput.txt"))?;
+        let mut bitmap = [false; 200];
+        for r in db.fresh_ranges() {
+            bitmap[r.from.0 as usize..=r.to.0 as usize].fill(true);
+        }
+
+        for step in 0..2000 {
+            let from = rng.random_range(0..190);
+            let r = range(from, from + rng.random_range(0..10));
+
+            match rng.random_range(0..100) {
+                0 => {
+                    db.clear();
+                    bitmap.fill(false);
+                }
+                n if n < 55 => {
+                    db.insert_range(r)?;
+                    bitmap[r.from.0 as usize..=r.to.0 as usize].fill(true);
+                }
+                _ => {
+                    db.remove_range(r)?;
+                    bitmap[r.from.0 as usize..=r.to.0 as usize].fill(false);
+                }
+            }
+
+            let rebuilt: IntervalSet<Id> = db.fresh_ranges().iter().copied().collect();
+            assert!((db.fresh() ^ &rebuilt).is_empty(), "step {}", step);
+
+            for (id, fresh) in bitmap.iter().enumerate() {
+                assert_eq!(
+                    db.is_fresh(Id(id as u64)),
+                    *fresh,
+                    "step {} id {}",
+                    step,
+                    id
+                );
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{Database, Id};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Classification {
+    /// Freshness of every id, in the order the ids came in.
+    pub fresh: Vec<bool>,
+    pub fresh_count: usize,
+    pub spoiled_count: usize,
+}
+
+impl Database {
+    /// Classifies a batch of ids with a single sweep over the merged ranges.
+    ///
+    /// The ids get sorted first, so the whole batch costs `O(m log m + n)`
+    /// instead of one lookup per id.
+    pub fn classify_all<I>(&self, ids: I) -> Classification
+    where
+        I: IntoIterator<Item = Id>,
+    {
+        let mut sorted: Vec<(Id, usize)> = ids.into_iter().zip(0..).collect();
+        sorted.sort_unstable_by_key(|(id, _)| *id);
+
+        let mut fresh = vec![false; sorted.len()];
+        let mut ranges = self.fresh().iter().peekable();
+
+        for (id, idx) in sorted {
+            while ranges.next_if(|range| range.to < id).is_some() {}
+
+            if ranges.peek().is_some_and(|range| range.from <= id) {
+                fresh[idx] = true;
+            }
+        }
+
+        let fresh_count = fresh.iter().filter(|f| **f).count();
+        Classification {
+            spoiled_count: fresh.len() - fresh_count,
+            fresh_count,
+            fresh,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use eyre::Result;
+
+    use super::*;
+    use crate::recorded_input;
+
+    #[test]
+    fn part_one_example() -> Result<()> {
+        let db = Database::from_input(include_str!("testinput.txt"))?;
+        let res = db.classify_all(db.ids.iter().copied());
+
+        assert_eq!(res.fresh, vec![false, true, false, true, true, false]);
+        assert_eq!((res.fresh_count, res.spoiled_count), (3, 3));
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let (input, db, expected) = recorded_input(1)?;
+
+        // ids streamed straight from the text
+        let ids = input
+            .lines()
+            .skip_while(|line| !line.is_empty())
+            .skip(1)
+            .filter_map(|line| Id::try_from_str(line).ok());
+        let res = db.classify_all(ids);
+
+        assert_eq!(res.fresh_count.to_string(), expected);
+        assert_eq!(
+            res.fresh,
+            db.ids.iter().map(|id| db.is_fresh(*id)).collect::<Vec<_>>()
+        );
+        Ok(())
+    }
+}
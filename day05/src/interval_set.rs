@@ -0,0 +1,439 @@
+use std::{
+    collections::BTreeMap,
+    ops::{BitAnd, BitOr, BitXor, Sub},
+};
+
+use aoc_core::Interval;
+use eyre::Result;
+
+use crate::{Id, span::Span};
+
+/// Values with a next one, so ranges that touch can be joined.
+pub trait Discrete: Ord + Copy {
+    /// The value right after `self`, `None` for the largest one.
+    fn successor(self) -> Option<Self>;
+}
+
+impl Discrete for Id {
+    fn successor(self) -> Option<Self> {
+        self.0.checked_add(1).map(Id)
+    }
+}
+
+/// Disjoint inclusive ranges kept sorted by their start.
+///
+/// Inserting a range swallows every stored range it overlaps or touches, so
+/// equal sets of values always store the same ranges and lookups only ever
+/// have to check the closest range starting at or before the value.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IntervalSet<T> {
+    // from -> to
+    ranges: BTreeMap<T, T>,
+}
+
+impl<T: Discrete> IntervalSet<T> {
+    pub fn new() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+        }
+    }
+
+    /// Adds the values of `range`, an empty one changes nothing.
+    pub fn insert(&mut self, range: Interval<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let mut new = range;
+
+        if let Some((&from, &to)) = self.ranges.range(..=new.from).next_back()
+            && to.successor().is_none_or(|next| next >= new.from)
+        {
+            new.from = from;
+            new.to = new.to.max(to);
+            self.ranges.remove(&from);
+        }
+
+        while let Some((&from, &to)) = self.ranges.range(new.from..).next()
+            && new.to.successor().is_none_or(|next| from <= next)
+        {
+            new.to = new.to.max(to);
+            self.ranges.remove(&from);
+        }
+
+        self.ranges.insert(new.from, new.to);
+    }
+
+    pub fn clear(&mut self) {
+        self.ranges.clear();
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, to)| *to >= value)
+    }
+
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Interval<T>> + '_ {
+        self.ranges.iter().map(|(from, to)| Interval {
+            from: *from,
+            to: *to,
+        })
+    }
+}
+
+impl IntervalSet<Id> {
+    /// Number of ids covered by the set, can't overflow even for `0..=u64::MAX`.
+    pub fn covered_len(&self) -> u128 {
+        self.iter().map(|range| range.len()).sum()
+    }
+}
+
+impl IntervalSet<Id> {
+    /// Takes the ids in `range` out of the set, splitting ranges that stick out on either side.
+    pub fn remove(&mut self, range: Interval<Id>) {
+        if range.from > range.to {
+            return;
+        }
+
+        // stored ranges are disjoint, so their ends are sorted as well
+        let overlapping: Vec<(Id, Id)> = self
+            .ranges
+            .range(..=range.to)
+            .rev()
+            .take_while(|(_, to)| **to >= range.from)
+            .map(|(from, to)| (*from, *to))
+            .collect();
+
+        for (from, to) in overlapping {
+            self.ranges.remove(&from);
+            if from < range.from {
+                self.ranges.insert(from, Id(range.from.0 - 1));
+            }
+            if to > range.to {
+                self.ranges.insert(Id(range.to.0 + 1), to);
+            }
+        }
+    }
+}
+
+// set algebra works on half-open `u128` bounds, so ranges ending at `u64::MAX` need no special casing
+impl IntervalSet<Id> {
+    pub fn union(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a || b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a && b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a && !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.combine(other, |a, b| a != b)
+    }
+
+    /// Ids inside `window` that the set doesn't cover.
+    ///
+    /// Fails for stepped windows, the result would be one range per id.
+    pub fn complement_within(&self, window: impl Into<Span>) -> Result<Self> {
+        Ok(match window.into().to_interval()? {
+            Some(window) => std::iter::once(window).collect::<Self>().difference(self),
+            None => Self::new(),
+        })
+    }
+
+    /// Sweeps the bounds of both sets in order, keeping the stretches where `keep` holds.
+    /// Touching or overlapping output ranges come out merged.
+    fn combine(&self, other: &Self, keep: impl Fn(bool, bool) -> bool) -> Self {
+        let bounds = |set: &Self| -> Vec<u128> {
+            set.ranges
+                .iter()
+                .flat_map(|(from, to)| [from.0 as u128, to.0 as u128 + 1])
+                .collect()
+        };
+        let (a, b) = (bounds(self), bounds(other));
+
+        let (mut i, mut j) = (0, 0);
+        let (mut in_a, mut in_b) = (false, false);
+        let mut start = None;
+        let mut out = vec![];
+
+        while i < a.len() || j < b.len() {
+            let at = a
+                .get(i)
+                .copied()
+                .unwrap_or(u128::MAX)
+                .min(b.get(j).copied().unwrap_or(u128::MAX));
+
+            // flip for every bound at this point before looking at the result, ranges
+            // that touch inside one set would otherwise produce empty pieces
+            while a.get(i) == Some(&at) {
+                in_a = !in_a;
+                i += 1;
+            }
+            while b.get(j) == Some(&at) {
+                in_b = !in_b;
+                j += 1;
+            }
+
+            match (start, keep(in_a, in_b)) {
+                (None, true) => start = Some(at),
+                (Some(from), false) => {
+                    out.push((Id(from as u64), Id((at - 1) as u64)));
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+
+        Self {
+            ranges: out.into_iter().collect(),
+        }
+    }
+}
+
+impl BitOr for &IntervalSet<Id> {
+    type Output = IntervalSet<Id>;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        self.union(rhs)
+    }
+}
+
+impl BitAnd for &IntervalSet<Id> {
+    type Output = IntervalSet<Id>;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        self.intersection(rhs)
+    }
+}
+
+impl Sub for &IntervalSet<Id> {
+    type Output = IntervalSet<Id>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self.difference(rhs)
+    }
+}
+
+impl BitXor for &IntervalSet<Id> {
+    type Output = IntervalSet<Id>;
+
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        self.symmetric_difference(rhs)
+    }
+}
+
+impl<T: Discrete> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::Database;
+
+    fn range(from: u64, to: u64) -> Interval<Id> {
+        Interval {
+            from: Id(from),
+            to: Id(to),
+        }
+    }
+
+    #[test]
+    fn insert_and_coalesce() {
+        let mut set = IntervalSet::new();
+        set.insert(range(10, 14));
+        set.insert(range(20, 25));
+        set.insert(range(1, 3));
+        assert_eq!(set.len(), 3);
+
+        set.insert(range(12, 21));
+        assert_eq!(
+            set.iter().map(|r| (r.from.0, r.to.0)).collect::<Vec<_>>(),
+            vec![(1, 3), (10, 25)]
+        );
+
+        assert!(set.contains(Id(3)));
+        assert!(!set.contains(Id(4)));
+        assert!(set.contains(Id(25)));
+        assert!(!set.contains(Id(26)));
+        assert_eq!(set.covered_len(), 3 + 16);
+
+        // touching ranges join up, reversed ones hold nothing
+        set.insert(range(4, 5));
+        set.insert(range(7, 6));
+        assert_eq!(
+            set.iter().map(|r| (r.from.0, r.to.0)).collect::<Vec<_>>(),
+            vec![(1, 5), (10, 25)]
+        );
+        assert_eq!(
+            [range(1, 3), range(4, 6)]
+                .into_iter()
+                .collect::<IntervalSet<_>>(),
+            [range(1, 6)].into_iter().collect()
+        );
+        let mut top = IntervalSet::new();
+        top.insert(range(u64::MAX, u64::MAX));
+        top.insert(range(5, u64::MAX - 1));
+        assert_eq!(top.len(), 1);
+
+        set.insert(range(0, u64::MAX));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.covered_len(), u64::MAX as u128 + 1);
+    }
+
+    #[test]
+    fn matches_brute_force_on_random_ranges() {
+        let mut rng = StdRng::seed_from_u64(5);
+
+        for _ in 0..200 {
+            let count = rng.random_range(1..60);
+            let ranges = (0..count)
+                .map(|_| {
+                    let from = rng.random_range(0..1000);
+                    range(from, from + rng.random_range(0..80))
+                })
+                .collect::<Vec<_>>();
+            let set: IntervalSet<Id> = ranges.iter().copied().collect();
+
+            let mut covered = 0;
+            for id in 0..1100 {
+                let expected = ranges.iter().any(|r| r.contains(Id(id)));
+                assert_eq!(set.contains(Id(id)), expected);
+                covered += expected as u128;
+            }
+            assert_eq!(set.covered_len(), covered);
+
+            // canonical: sorted, and no two ranges overlap or touch
+            let stored = set.iter().collect::<Vec<_>>();
+            assert!(stored.windows(2).all(|w| w[0].to.0 + 1 < w[1].from.0));
+            assert_eq!(set, &set | &IntervalSet::new());
+        }
+    }
+
+    type Membership = fn(bool, bool) -> bool;
+
+    fn set(ranges: &[(u64, u64)]) -> IntervalSet<Id> {
+        ranges.iter().map(|(from, to)| range(*from, *to)).collect()
+    }
+
+    fn pairs(set: &IntervalSet<Id>) -> Vec<(u64, u64)> {
+        set.iter().map(|r| (r.from.0, r.to.0)).collect()
+    }
+
+    #[test]
+    fn set_operators() -> Result<()> {
+        let a = set(&[(1, 10), (20, 30)]);
+        let b = set(&[(5, 22), (30, 30), (40, 45)]);
+
+        assert_eq!(pairs(&(&a | &b)), vec![(1, 30), (40, 45)]);
+        assert_eq!(pairs(&(&a & &b)), vec![(5, 10), (20, 22), (30, 30)]);
+        assert_eq!(pairs(&(&a - &b)), vec![(1, 4), (23, 29)]);
+        assert_eq!(
+            pairs(&(&a ^ &b)),
+            vec![(1, 4), (11, 19), (23, 29), (40, 45)]
+        );
+
+        // touching ranges come out as one
+        assert_eq!(pairs(&(&set(&[(1, 3)]) | &set(&[(4, 6)]))), vec![(1, 6)]);
+
+        assert_eq!(
+            pairs(&a.complement_within(range(0, 25))?),
+            vec![(0, 0), (11, 19)]
+        );
+        assert_eq!(
+            pairs(&a.complement_within(Span::exclusive(Id(10), Id(20)))?),
+            vec![(11, 19)]
+        );
+        assert_eq!(
+            pairs(&set(&[(5, u64::MAX)]).complement_within(range(0, u64::MAX))?),
+            vec![(0, 4)]
+        );
+        assert!(
+            a.complement_within(Span::exclusive(Id(3), Id(3)))?
+                .is_empty()
+        );
+        assert!(
+            a.complement_within(Span::from(0..=25).with_step(2)?)
+                .is_err()
+        );
+        assert_eq!(
+            pairs(&(&set(&[(0, u64::MAX)]) ^ &set(&[(0, 0)]))),
+            vec![(1, u64::MAX)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn set_operators_on_random_sets() {
+        let mut rng = StdRng::seed_from_u64(32);
+        let random_set = |rng: &mut StdRng| {
+            (0..rng.random_range(0..8))
+                .map(|_| {
+                    let from = rng.random_range(0..100);
+                    range(from, from + rng.random_range(0..15))
+                })
+                .collect::<IntervalSet<Id>>()
+        };
+
+        for _ in 0..300 {
+            let a = random_set(&mut rng);
+            let b = random_set(&mut rng);
+
+            let checks: [(_, Membership); 4] = [
+                (&a | &b, |a, b| a || b),
+                (&a & &b, |a, b| a && b),
+                (&a - &b, |a, b| a && !b),
+                (&a ^ &b, |a, b| a != b),
+            ];
+
+            for (result, expected) in checks {
+                for id in 0..120 {
+                    let id = Id(id);
+                    assert_eq!(
+                        result.contains(id),
+                        expected(a.contains(id), b.contains(id))
+                    );
+                }
+
+                // canonical: no two ranges touch
+                let ranges = pairs(&result);
+                assert!(ranges.windows(2).all(|w| w[0].1 + 1 < w[1].0));
+            }
+        }
+    }
+
+    #[test]
+    fn spoiled_in_window() -> Result<()> {
+        let db = Database::from_input(include_str!("testinput.txt"))?;
+        let window = range(1, 10u64.pow(12));
+        let spoiled = db.fresh().complement_within(window)?;
+
+        let fresh_in_window = db.fresh() & &std::iter::once(window).collect();
+        assert_eq!(
+            spoiled.covered_len() + fresh_in_window.covered_len(),
+            10u128.pow(12)
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,166 @@
+use std::{num::ParseIntError, str::FromStr};
+
+use aoc_core::{Interval, Solution};
+use eyre::Result;
+
+pub mod aggregate;
+pub mod changes;
+pub mod classify;
+pub mod interval_set;
+pub mod parse;
+pub mod provenance;
+pub mod span;
+pub mod store;
+
+use changes::Listeners;
+use interval_set::IntervalSet;
+use parse::{ParseIssue, ParseMode, parse_database};
+use provenance::{ProvenanceIndex, Source};
+
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd, Ord, Eq)]
+pub struct Id(pub u64);
+
+impl Id {
+    pub fn try_from_str(value: &str) -> Result<Self> {
+        Ok(Self(value.parse()?))
+    }
+}
+
+impl FromStr for Id {
+    type Err = ParseIntError;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        Ok(Self(value.parse()?))
+    }
+}
+
+/// Lets `Interval<Id>::len` count ids.
+impl From<Id> for i128 {
+    fn from(id: Id) -> Self {
+        id.0 as i128
+    }
+}
+
+#[derive(Debug)]
+pub struct Database {
+    fresh_ranges: Vec<Interval<Id>>,
+    /// Input line of each entry in `fresh_ranges`, 0 for ranges that didn't come from a text line.
+    fresh_range_lines: Vec<usize>,
+    pub ids: Vec<Id>,
+    fresh: IntervalSet<Id>,
+    listeners: Listeners,
+}
+
+impl Database {
+    pub fn from_input(value: &str) -> Result<Self> {
+        Ok(parse_database(value, ParseMode::Strict)?.0)
+    }
+
+    /// Same as `from_input`, but lets the caller skip bad lines and get them back as warnings.
+    pub fn from_input_with_mode(value: &str, mode: ParseMode) -> Result<(Self, Vec<ParseIssue>)> {
+        Ok(parse_database(value, mode)?)
+    }
+
+    pub fn is_fresh(&self, id: Id) -> bool {
+        self.fresh.contains(id)
+    }
+
+    pub fn fresh(&self) -> &IntervalSet<Id> {
+        &self.fresh
+    }
+
+    /// The ranges as they were given, overlaps and all, minus whatever was removed since.
+    pub fn fresh_ranges(&self) -> &[Interval<Id>] {
+        &self.fresh_ranges
+    }
+
+    /// Input line of each entry in `fresh_ranges`.
+    pub fn fresh_range_lines(&self) -> &[usize] {
+        &self.fresh_range_lines
+    }
+
+    /// Index answering which of the original range lines cover an id.
+    pub fn provenance(&self) -> ProvenanceIndex {
+        ProvenanceIndex::new(
+            self.fresh_ranges
+                .iter()
+                .zip(&self.fresh_range_lines)
+                .map(|(range, line)| Source {
+                    range: *range,
+                    line: *line,
+                })
+                .collect(),
+        )
+    }
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn part_one(&self, input: &str) -> Result<String> {
+        let db = Database::from_input(input)?;
+        let fresh = db.ids.iter().filter(|id| db.is_fresh(**id)).count();
+        Ok(fresh.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Result<String> {
+        Ok(Database::from_input(input)?
+            .fresh()
+            .covered_len()
+            .to_string())
+    }
+}
+
+/// The puzzle input, its database and the recorded answer to `part`, for the tests.
+#[cfg(test)]
+fn recorded_input(part: u8) -> Result<(String, Database, String)> {
+    let src = std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/src"));
+    let (input, expected) = aoc_core::answers::recorded(src, 5, "input", part)?;
+    let db = Database::from_input(&input)?;
+    Ok((input, db, expected))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn part_one_example() -> Result<()> {
+        let db = Database::from_input(include_str!("testinput.txt"))?;
+        let res = db.ids.iter().filter(|id| db.is_fresh(**id)).count();
+        assert_eq!(res, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let (_, db, expected) = recorded_input(1)?;
+        let res = db.ids.iter().filter(|id| db.is_fresh(**id)).count();
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_example() -> Result<()> {
+        let db = Database::from_input(include_str!("testinput.txt"))?;
+        let set: IntervalSet<Id> = db.fresh_ranges().iter().copied().collect();
+
+        let res = set.iter().map(|range| range.len()).sum::<u128>();
+        assert_eq!(res, 14);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_solution() -> Result<()> {
+        let (_, db, expected) = recorded_input(2)?;
+        let set: IntervalSet<Id> = db.fresh_ranges().iter().copied().collect();
+
+        let res = set.iter().map(|range| range.len()).sum::<u128>();
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,227 @@
+use std::fmt;
+
+use aoc_core::Interval;
+
+use crate::{Database, Id};
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ParseMode {
+    /// First problem fails the whole parse.
+    #[default]
+    Strict,
+    /// Bad lines are skipped and reported as warnings.
+    Lenient,
+}
+
+/// Something wrong with the input, `line` and `column` are 1-based.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseIssue {
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )
+    }
+}
+
+impl std::error::Error for ParseIssue {}
+
+impl ParseIssue {
+    fn new(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+}
+
+fn parse_id(token: &str, line: usize, column: usize) -> Result<Id, ParseIssue> {
+    token
+        .parse()
+        .map(Id)
+        .map_err(|e| ParseIssue::new(line, column, format!("invalid id {:?}: {}", token, e)))
+}
+
+fn parse_range(value: &str, line: usize) -> Result<Interval<Id>, ParseIssue> {
+    let indent = value.len() - value.trim_start().len();
+    let value = value.trim();
+
+    let (from, to) = value.split_once('-').ok_or(ParseIssue::new(
+        line,
+        indent + 1,
+        "expected a range like `3-5`",
+    ))?;
+    let to_column = indent + from.len() + 2;
+
+    if to.contains('-') {
+        let extra = to_column + to.find('-').unwrap_or(0);
+        return Err(ParseIssue::new(line, extra, "unexpected `-`"));
+    }
+
+    let range = Interval {
+        from: parse_id(from, line, indent + 1)?,
+        to: parse_id(to, line, to_column)?,
+    };
+
+    if range.from > range.to {
+        return Err(ParseIssue::new(
+            line,
+            indent + 1,
+            format!("reversed range {}-{}", range.from.0, range.to.0),
+        ));
+    }
+
+    Ok(range)
+}
+
+/// Parses the ranges section, a blank line, then the ids section.
+pub fn parse_database(
+    value: &str,
+    mode: ParseMode,
+) -> Result<(Database, Vec<ParseIssue>), ParseIssue> {
+    let mut warnings = vec![];
+    let mut report = |issue: ParseIssue| match mode {
+        ParseMode::Strict => Err(issue),
+        ParseMode::Lenient => {
+            warnings.push(issue);
+            Ok(())
+        }
+    };
+
+    let mut ranges = vec![];
+    let mut range_lines = vec![];
+    let mut ids = vec![];
+    let mut in_ids = false;
+    let mut line_count = 0;
+
+    for (idx, raw) in value.lines().enumerate() {
+        let line = idx + 1;
+        line_count = line;
+
+        if raw.trim().is_empty() {
+            in_ids = true;
+            continue;
+        }
+
+        if in_ids {
+            let indent = raw.len() - raw.trim_start().len();
+            match parse_id(raw.trim(), line, indent + 1) {
+                Ok(id) => ids.push(id),
+                Err(issue) => report(issue)?,
+            }
+            continue;
+        }
+
+        match parse_range(raw, line) {
+            Ok(range) => {
+                ranges.push(range);
+                range_lines.push(line);
+            }
+            Err(issue) => {
+                // a bare id here most likely means the blank separator is missing
+                if mode == ParseMode::Lenient
+                    && !raw.contains('-')
+                    && let Ok(id) = raw.trim().parse()
+                {
+                    report(ParseIssue::new(line, 1, "missing blank line before ids"))?;
+                    ids.push(Id(id));
+                    in_ids = true;
+                    continue;
+                }
+                report(issue)?;
+            }
+        }
+    }
+
+    if !in_ids {
+        report(ParseIssue::new(
+            line_count + 1,
+            1,
+            "missing blank line between ranges and ids",
+        ))?;
+    }
+
+    Ok((
+        Database {
+            fresh: ranges.iter().copied().collect(),
+            fresh_ranges: ranges,
+            fresh_range_lines: range_lines,
+            ids,
+            listeners: Default::default(),
+        },
+        warnings,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_core::input;
+
+    use super::*;
+
+    fn strict_error(input: &str) -> ParseIssue {
+        parse_database(input, ParseMode::Strict).unwrap_err()
+    }
+
+    #[test]
+    fn strict_reports_position() {
+        assert_eq!(
+            strict_error("3-5\n10-1x4\n\n1\n"),
+            ParseIssue::new(2, 4, "invalid id \"1x4\": invalid digit found in string")
+        );
+        assert_eq!(
+            strict_error("3-5\n14-10\n\n1\n"),
+            ParseIssue::new(2, 1, "reversed range 14-10")
+        );
+        assert_eq!(
+            strict_error("3-5-7\n\n1\n"),
+            ParseIssue::new(1, 4, "unexpected `-`")
+        );
+        assert_eq!(
+            strict_error("3-5\n\n1\n  x\n"),
+            ParseIssue::new(4, 3, "invalid id \"x\": invalid digit found in string")
+        );
+        assert_eq!(
+            strict_error("3-5\n1\n5\n"),
+            ParseIssue::new(2, 1, "expected a range like `3-5`")
+        );
+        assert_eq!(
+            strict_error("3-5\n10-14"),
+            ParseIssue::new(3, 1, "missing blank line between ranges and ids")
+        );
+    }
+
+    #[test]
+    fn lenient_collects_warnings() -> Result<(), ParseIssue> {
+        let (db, warnings) = parse_database("3-5\n9-7\n10-14\n1\n5\n?\n11\n", ParseMode::Lenient)?;
+
+        assert_eq!(db.fresh_ranges().len(), 2);
+        assert_eq!(db.ids, vec![Id(1), Id(5), Id(11)]);
+        assert_eq!(
+            warnings
+                .iter()
+                .map(|w| (w.line, w.column))
+                .collect::<Vec<_>>(),
+            vec![(2, 1), (4, 1), (6, 1)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn strict_accepts_inputs() -> eyre::Result<()> {
+        for name in ["testinput.txt", "input.txt"] {
+            let input = input::read(&format!("{}/src/{}", env!("CARGO_MANIFEST_DIR"), name))?;
+            let (_, warnings) = parse_database(&input, ParseMode::Strict)?;
+            assert!(warnings.is_empty());
+        }
+        Ok(())
+    }
+}
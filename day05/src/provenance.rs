@@ -0,0 +1,214 @@
+use aoc_core::Interval;
+
+use crate::{Id, span::Span};
+
+/// One of the original range lines, `line` is 1-based.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Source {
+    pub range: Interval<Id>,
+    pub line: usize,
+}
+
+/// Static index over the unmerged ranges for overlap queries in `O(log n + k)`.
+///
+/// The sources are sorted by start, so the ones starting inside or before a
+/// query form a prefix found by binary search. A sparse table over the range
+/// ends picks the longest reaching source of any slice in `O(1)`; if it ends
+/// before the query nothing in the slice overlaps, otherwise it is a hit and
+/// both sides of it get split the same way. Every split costs a hit or closes
+/// off a slice, which keeps the walk at `O(k)` after the search.
+#[derive(Debug, Clone)]
+pub struct ProvenanceIndex {
+    sources: Vec<Source>,
+    // sparse[k][i]: source with the largest end among sources i..i + 2^k
+    sparse: Vec<Vec<usize>>,
+}
+
+impl ProvenanceIndex {
+    pub fn new(mut sources: Vec<Source>) -> Self {
+        sources.sort_by_key(|s| (s.range.from, s.range.to, s.line));
+
+        let mut index = Self {
+            sources,
+            sparse: vec![],
+        };
+        index.build_sparse();
+        index
+    }
+
+    fn build_sparse(&mut self) {
+        let mut level: Vec<usize> = (0..self.sources.len()).collect();
+        let mut width = 1;
+
+        while !level.is_empty() {
+            let next = (0..level.len().saturating_sub(width))
+                .map(|i| self.further(level[i], level[i + width]))
+                .collect();
+            self.sparse.push(level);
+            level = next;
+            width *= 2;
+        }
+    }
+
+    fn further(&self, a: usize, b: usize) -> usize {
+        if self.sources[b].range.to > self.sources[a].range.to {
+            b
+        } else {
+            a
+        }
+    }
+
+    // source reaching furthest among lo..hi, which must not be empty
+    fn furthest_between(&self, lo: usize, hi: usize) -> usize {
+        let level = (hi - lo).ilog2() as usize;
+        self.further(
+            self.sparse[level][lo],
+            self.sparse[level][hi - (1 << level)],
+        )
+    }
+
+    /// Every source range containing `id`, ordered by range start.
+    pub fn covering(&self, id: Id) -> Vec<Source> {
+        self.overlapping(Span::inclusive(id, id))
+    }
+
+    /// Every source range sharing at least one id with `span`, ordered by range start.
+    ///
+    /// With a step, ranges that fit between two of the span's ids are left out
+    /// after the walk, so `k` counts every range overlapping the span's bounds.
+    pub fn overlapping(&self, span: impl Into<Span>) -> Vec<Source> {
+        let span = span.into();
+        if span.is_empty() {
+            return vec![];
+        }
+
+        let mut out = self.overlapping_bounds(span.ids);
+        if span.step.is_some() {
+            out.retain(|source| !span.clip(source.range).is_empty());
+        }
+        out
+    }
+
+    fn overlapping_bounds(&self, query: Interval<Id>) -> Vec<Source> {
+        enum Task {
+            Split(usize, usize),
+            Emit(usize),
+        }
+
+        let end = self.sources.partition_point(|s| s.range.from <= query.to);
+        let mut out = vec![];
+        let mut tasks = vec![Task::Split(0, end)];
+
+        // the left side goes on top, so hits come out in start order
+        while let Some(task) = tasks.pop() {
+            match task {
+                Task::Emit(i) => out.push(self.sources[i]),
+                Task::Split(lo, hi) if lo < hi => {
+                    let i = self.furthest_between(lo, hi);
+                    if self.sources[i].range.to >= query.from {
+                        tasks.extend([Task::Split(i + 1, hi), Task::Emit(i), Task::Split(lo, i)]);
+                    }
+                }
+                Task::Split(..) => {}
+            }
+        }
+        out
+    }
+
+    pub fn len(&self) -> usize {
+        self.sources.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sources.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::{Database, recorded_input};
+
+    fn range(from: u64, to: u64) -> Interval<Id> {
+        Interval {
+            from: Id(from),
+            to: Id(to),
+        }
+    }
+
+    #[test]
+    fn example() -> eyre::Result<()> {
+        let db = Database::from_input(include_str!("testinput.txt"))?;
+        let index = db.provenance();
+
+        let lines = |sources: Vec<Source>| sources.iter().map(|s| s.line).collect::<Vec<_>>();
+        assert_eq!(lines(index.covering(Id(17))), vec![4, 3]);
+        assert_eq!(lines(index.covering(Id(5))), vec![1]);
+        assert!(index.covering(Id(8)).is_empty());
+        assert_eq!(lines(index.overlapping(range(6, 11))), vec![2]);
+        assert_eq!(lines(index.overlapping(range(0, 100))), vec![1, 2, 4, 3]);
+        assert!(index.overlapping(Span::exclusive(Id(5), Id(10))).is_empty());
+        assert!(index.overlapping(range(9, 8)).is_empty());
+
+        // 6, 15 and 24: lines 2 and 3 sit between them, only line 4 holds 15
+        let stepped = Span::inclusive(Id(6), Id(30)).with_step(9)?;
+        assert_eq!(lines(index.overlapping(stepped)), vec![4]);
+        Ok(())
+    }
+
+    #[test]
+    fn matches_scan_on_random_ranges() -> eyre::Result<()> {
+        let mut rng = StdRng::seed_from_u64(35);
+
+        for _ in 0..100 {
+            let sources = (0..rng.random_range(0..50))
+                .map(|line| {
+                    let from = rng.random_range(0..500);
+                    Source {
+                        range: range(from, from + rng.random_range(0..60)),
+                        line: line + 1,
+                    }
+                })
+                .collect::<Vec<_>>();
+            let index = ProvenanceIndex::new(sources.clone());
+
+            for _ in 0..50 {
+                let from = rng.random_range(0..600);
+                let query = Span::from(from..=from + rng.random_range(0..20))
+                    .with_step(rng.random_range(1..5))?;
+
+                let mut expected = sources
+                    .iter()
+                    .filter(|s| query.iter().any(|id| s.range.contains(id)))
+                    .map(|s| s.line)
+                    .collect::<Vec<_>>();
+                let hits = index.overlapping(query);
+                assert!(hits.is_sorted_by_key(|s| s.range.from));
+
+                let mut found = hits.iter().map(|s| s.line).collect::<Vec<_>>();
+                expected.sort();
+                found.sort();
+                assert_eq!(found, expected);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn every_fresh_id_has_a_source() -> eyre::Result<()> {
+        let (_, db, expected) = recorded_input(1)?;
+        let index = db.provenance();
+
+        let mut sourced = 0;
+        for id in db.ids.iter() {
+            let sources = index.covering(*id);
+            assert_eq!(!sources.is_empty(), db.is_fresh(*id));
+            assert!(sources.iter().all(|s| s.range.contains(*id)));
+            sourced += !sources.is_empty() as usize;
+        }
+        assert_eq!(sourced.to_string(), expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    num::NonZeroU64,
+    ops::{Bound, RangeInclusive},
+};
+
+use aoc_core::Interval;
+use eyre::{Result, eyre};
+
+use crate::Id;
+
+/// Range of ids with any kind of bounds and an optional step.
+///
+/// The bounds are resolved to the closed interval they leave when the span is
+/// built, missing ones stand for the ends of the id space. With a step of `k`
+/// only every k-th id counting from the first included one is part of the span.
+#[derive(Debug, Clone, Copy)]
+pub struct Span {
+    /// Empty when the bounds leave no id.
+    pub ids: Interval<Id>,
+    pub step: Option<NonZeroU64>,
+}
+
+impl Span {
+    pub fn new(start: Bound<Id>, end: Bound<Id>) -> Self {
+        let first = match start {
+            Bound::Included(id) => Some(id.0),
+            Bound::Excluded(id) => id.0.checked_add(1),
+            Bound::Unbounded => Some(0),
+        };
+        let last = match end {
+            Bound::Included(id) => Some(id.0),
+            Bound::Excluded(id) => id.0.checked_sub(1),
+            Bound::Unbounded => Some(u64::MAX),
+        };
+
+        let ids = match (first, last) {
+            (Some(first), Some(last)) => Interval::new(Id(first), Id(last)),
+            _ => return Self::empty(),
+        };
+        Self { ids, step: None }
+    }
+
+    fn empty() -> Self {
+        Self {
+            ids: Interval::new(Id(1), Id(0)),
+            step: None,
+        }
+    }
+
+    /// `from..=to`
+    pub fn inclusive(from: Id, to: Id) -> Self {
+        Self::new(Bound::Included(from), Bound::Included(to))
+    }
+
+    /// `from..to`
+    pub fn half_open(from: Id, to: Id) -> Self {
+        Self::new(Bound::Included(from), Bound::Excluded(to))
+    }
+
+    /// Both ends left out.
+    pub fn exclusive(from: Id, to: Id) -> Self {
+        Self::new(Bound::Excluded(from), Bound::Excluded(to))
+    }
+
+    pub fn unbounded() -> Self {
+        Self::new(Bound::Unbounded, Bound::Unbounded)
+    }
+
+    pub fn with_step(self, step: u64) -> Result<Self> {
+        Ok(Self {
+            step: Some(NonZeroU64::new(step).ok_or(eyre!("step must not be zero"))?),
+            ..self
+        })
+    }
+
+    fn step(&self) -> u64 {
+        self.step.map_or(1, NonZeroU64::get)
+    }
+
+    /// Number of ids in the span, exact even for the whole `u64` space.
+    pub fn len(&self) -> u128 {
+        match self.ids.len() {
+            0 => 0,
+            len => (len - 1) / self.step() as u128 + 1,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    pub fn contains(&self, id: Id) -> bool {
+        self.ids.contains(id) && (id.0 - self.ids.from.0).is_multiple_of(self.step())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Id> + use<> {
+        let step = self.step();
+        let last = self.ids.to.0;
+        let first = (!self.is_empty()).then_some(self.ids.from.0);
+        std::iter::successors(first, move |id| {
+            id.checked_add(step).filter(|id| *id <= last)
+        })
+        .map(Id)
+    }
+
+    /// The ids of the span that also lie in `range`, with the same step.
+    pub fn clip(&self, range: Interval<Id>) -> Self {
+        let from = self.ids.from.max(range.from);
+        let to = self.ids.to.min(range.to);
+        if self.is_empty() || from > to {
+            return Self::empty();
+        }
+
+        // move up to the next id that is on the step
+        let step = self.step();
+        let first = match (from.0 - self.ids.from.0) % step {
+            0 => Some(from.0),
+            offset => from.0.checked_add(step - offset),
+        };
+        match first {
+            Some(first) if first <= to.0 => Self {
+                ids: Interval::new(Id(first), to),
+                step: self.step,
+            },
+            _ => Self::empty(),
+        }
+    }
+
+    /// The same ids as a plain interval, `None` when empty.
+    ///
+    /// Fails for stepped spans, which can't be written as one interval.
+    pub fn to_interval(&self) -> Result<Option<Interval<Id>>> {
+        if self.is_empty() {
+            return Ok(None);
+        }
+        if self.step() != 1 && self.ids.from != self.ids.to {
+            return Err(eyre!("stepped span is not contiguous"));
+        }
+        Ok(Some(self.ids))
+    }
+}
+
+/// Equal when both spans hold the same ids, however they were built.
+impl PartialEq for Span {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.len(), other.len()) {
+            (0, 0) => true,
+            (a, b) if a != b => false,
+            (1, _) => self.ids.from == other.ids.from,
+            _ => self.ids.from == other.ids.from && self.step() == other.step(),
+        }
+    }
+}
+
+impl Eq for Span {}
+
+impl From<Interval<Id>> for Span {
+    fn from(ids: Interval<Id>) -> Self {
+        Self::inclusive(ids.from, ids.to)
+    }
+}
+
+impl From<RangeInclusive<u64>> for Span {
+    fn from(range: RangeInclusive<u64>) -> Self {
+        Self::inclusive(Id(*range.start()), Id(*range.end()))
+    }
+}
+
+impl TryFrom<Span> for RangeInclusive<u64> {
+    type Error = eyre::ErrReport;
+
+    fn try_from(span: Span) -> Result<Self> {
+        let ids = span.to_interval()?.ok_or(eyre!("empty span"))?;
+        Ok(ids.from.0..=ids.to.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn bound_kinds() -> Result<()> {
+        let (a, b) = (Id(3), Id(7));
+
+        assert_eq!(Span::inclusive(a, b).len(), 5);
+        assert_eq!(Span::half_open(a, b).len(), 4);
+        assert_eq!(Span::exclusive(a, b).len(), 3);
+        assert!(Span::exclusive(a, Id(4)).is_empty());
+        assert!(!Span::exclusive(a, b).contains(a));
+        assert!(Span::half_open(a, b).contains(a));
+        assert!(!Span::half_open(a, b).contains(b));
+
+        assert_eq!(
+            Span::new(Bound::Excluded(Id(u64::MAX)), Bound::Unbounded).len(),
+            0
+        );
+        assert_eq!(Span::new(Bound::Unbounded, Bound::Excluded(Id(0))).len(), 0);
+        assert_eq!(Span::unbounded().len(), u64::MAX as u128 + 1);
+        Ok(())
+    }
+
+    #[test]
+    fn stepped() -> Result<()> {
+        let span = Span::inclusive(Id(3), Id(20)).with_step(4)?;
+
+        assert_eq!(span.len(), 5);
+        assert_eq!(
+            span.iter().map(|id| id.0).collect::<Vec<_>>(),
+            vec![3, 7, 11, 15, 19]
+        );
+        assert!(span.contains(Id(15)));
+        assert!(!span.contains(Id(16)));
+        assert!(span.to_interval().is_err());
+
+        assert_eq!(Span::unbounded().with_step(2)?.len(), 1 << 63);
+        assert!(Span::unbounded().with_step(0).is_err());
+
+        let top = Span::inclusive(Id(u64::MAX - 5), Id(u64::MAX)).with_step(u64::MAX)?;
+        assert_eq!(top.iter().collect::<Vec<_>>(), vec![Id(u64::MAX - 5)]);
+        assert_eq!(
+            Span::inclusive(Id(u64::MAX - 4), Id(u64::MAX))
+                .with_step(2)?
+                .iter()
+                .count(),
+            3
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn clip() -> Result<()> {
+        let span = Span::inclusive(Id(3), Id(20)).with_step(4)?;
+        let clipped = span.clip(Interval::new(Id(8), Id(16)));
+
+        assert_eq!(
+            clipped.iter().map(|id| id.0).collect::<Vec<_>>(),
+            vec![11, 15]
+        );
+        assert!(span.clip(Interval::new(Id(12), Id(14))).is_empty());
+        assert!(span.clip(Interval::new(Id(21), Id(30))).is_empty());
+        assert_eq!(
+            Span::unbounded()
+                .with_step(u64::MAX)?
+                .clip(Interval::new(Id(1), Id(u64::MAX - 1)))
+                .len(),
+            0
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn equality() -> Result<()> {
+        assert_eq!(
+            Span::exclusive(Id(5), Id(6)),
+            Span::exclusive(Id(90), Id(10))
+        );
+        assert_eq!(Span::exclusive(Id(5), Id(6)), Span::half_open(Id(0), Id(0)));
+        assert_eq!(Span::half_open(Id(3), Id(8)), Span::exclusive(Id(2), Id(8)));
+        assert_eq!(
+            Span::inclusive(Id(3), Id(5)).with_step(9)?,
+            Span::inclusive(Id(3), Id(3))
+        );
+        assert_eq!(
+            Span::inclusive(Id(3), Id(20)).with_step(4)?,
+            Span::inclusive(Id(3), Id(19)).with_step(4)?
+        );
+        assert_ne!(
+            Span::inclusive(Id(3), Id(20)).with_step(4)?,
+            Span::inclusive(Id(3), Id(20)).with_step(2)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn conversions() -> Result<()> {
+        let span = Span::from(5..=9);
+        assert_eq!(RangeInclusive::try_from(span)?, 5..=9);
+        assert_eq!(
+            RangeInclusive::try_from(Span::exclusive(Id(5), Id(9)))?,
+            6..=8
+        );
+        assert!(RangeInclusive::try_from(Span::exclusive(Id(5), Id(6))).is_err());
+
+        let ids = Interval::new(Id(0), Id(u64::MAX));
+        assert_eq!(ids.len(), u64::MAX as u128 + 1);
+        assert_eq!(RangeInclusive::try_from(Span::from(ids))?, 0..=u64::MAX);
+        assert_eq!(Span::from(ids).to_interval()?, Some(ids));
+        Ok(())
+    }
+}
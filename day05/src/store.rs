@@ -0,0 +1,491 @@
+use std::{fs, io::Write, path::Path};
+
+use aoc_core::Interval;
+use eyre::{Result, eyre};
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+
+use crate::{Database, Id};
+
+// Binary layout, all fixed-width numbers little endian:
+//
+//   header       MAGIC, version u16, reserved u16, then u64s: range count,
+//                id count, block count, ranges offset, ids offset, checksum
+//   block index  per block of BLOCK_SIZE ranges: `from` of its first range
+//                and byte offset of that range relative to the ranges section
+//   ranges       varint(from - previous to), varint(to - from); the first range
+//                of a block is relative to the block's `from` instead
+//   ids          varint(zigzag(id - previous id)), in the original order
+//
+// The checksum is FNV-1a over the whole file, with the checksum field read as
+// zero. The ids end exactly at the end of the file. The block index lets a
+// memory-mapped file answer lookups after decoding at most one block.
+
+const MAGIC: &[u8; 4] = b"D5DB";
+const VERSION: u16 = 1;
+const HEADER_LEN: usize = 56;
+const BLOCK_SIZE: usize = 64;
+const CHECKSUM_AT: usize = HEADER_LEN - 8;
+
+fn fnv1a<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u64 {
+    bytes.into_iter().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ *b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+// callers make sure the file holds at least a header
+fn file_checksum(file: &[u8]) -> u64 {
+    fnv1a(
+        file[..CHECKSUM_AT]
+            .iter()
+            .chain(&[0; 8])
+            .chain(&file[HEADER_LEN..]),
+    )
+}
+
+fn seal(file: &mut [u8]) {
+    let checksum = file_checksum(file);
+    file[CHECKSUM_AT..HEADER_LEN].copy_from_slice(&checksum.to_le_bytes());
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn read_varint(bytes: &[u8], pos: &mut usize) -> Result<u64> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = *bytes.get(*pos).ok_or(eyre!("truncated varint"))?;
+        *pos += 1;
+        value |= ((byte & 0x7f) as u64) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(eyre!("varint too long"))
+}
+
+fn read_u64(bytes: &[u8], pos: usize) -> Result<u64> {
+    let chunk = pos
+        .checked_add(8)
+        .and_then(|end| bytes.get(pos..end))
+        .ok_or(eyre!("truncated file at byte {}", pos))?;
+    Ok(u64::from_le_bytes(chunk.try_into()?))
+}
+
+fn read_offset(bytes: &[u8], pos: usize) -> Result<usize> {
+    let value = read_u64(bytes, pos)?;
+    usize::try_from(value).map_err(|_| eyre!("offset {} at byte {} doesn't fit", value, pos))
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Header {
+    range_count: usize,
+    id_count: usize,
+    block_count: usize,
+    ranges_offset: usize,
+    ids_offset: usize,
+    checksum: u64,
+}
+
+impl Header {
+    fn parse(bytes: &[u8]) -> Result<Self> {
+        if bytes.len() < HEADER_LEN || &bytes[..4] != MAGIC {
+            return Err(eyre!("not a freshness database"));
+        }
+        let version = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if version != VERSION {
+            return Err(eyre!("unsupported version {}", version));
+        }
+
+        let field = |i: usize| read_offset(bytes, 8 + i * 8);
+        let header = Self {
+            range_count: field(0)?,
+            id_count: field(1)?,
+            block_count: field(2)?,
+            ranges_offset: field(3)?,
+            ids_offset: field(4)?,
+            checksum: read_u64(bytes, CHECKSUM_AT)?,
+        };
+
+        let ranges_offset = header
+            .block_count
+            .checked_mul(16)
+            .and_then(|index_len| index_len.checked_add(HEADER_LEN));
+        if header.block_count != header.range_count.div_ceil(BLOCK_SIZE)
+            || Some(header.ranges_offset) != ranges_offset
+            || header.ids_offset < header.ranges_offset
+            || header.ids_offset > bytes.len()
+        {
+            return Err(eyre!("corrupt header"));
+        }
+
+        Ok(header)
+    }
+
+    fn block(&self, bytes: &[u8], idx: usize) -> Result<(u64, usize)> {
+        let pos = HEADER_LEN + idx * 16;
+        let offset = self
+            .ranges_offset
+            .checked_add(read_offset(bytes, pos + 8)?)
+            .ok_or(eyre!("block {} out of bounds", idx))?;
+        Ok((read_u64(bytes, pos)?, offset))
+    }
+
+    /// Decodes the ranges of one block.
+    fn block_ranges<'a>(
+        &self,
+        bytes: &'a [u8],
+        idx: usize,
+    ) -> Result<impl Iterator<Item = Result<Interval<Id>>> + 'a> {
+        let (mut prev, mut pos) = self.block(bytes, idx)?;
+        let count = BLOCK_SIZE.min(self.range_count - idx * BLOCK_SIZE);
+
+        Ok((0..count).map(move |_| {
+            let from = prev
+                .checked_add(read_varint(bytes, &mut pos)?)
+                .ok_or(eyre!("range out of bounds"))?;
+            let to = from
+                .checked_add(read_varint(bytes, &mut pos)?)
+                .ok_or(eyre!("range out of bounds"))?;
+            prev = to;
+            Ok(Interval {
+                from: Id(from),
+                to: Id(to),
+            })
+        }))
+    }
+
+    fn ranges<'a>(&self, bytes: &'a [u8]) -> impl Iterator<Item = Result<Interval<Id>>> + 'a {
+        let header = *self;
+        (0..self.block_count).flat_map(move |idx| match header.block_ranges(bytes, idx) {
+            Ok(ranges) => Box::new(ranges) as Box<dyn Iterator<Item = _>>,
+            Err(e) => Box::new(std::iter::once(Err(e))),
+        })
+    }
+
+    /// Byte right after the last id.
+    fn ids_end(&self, bytes: &[u8]) -> Result<usize> {
+        let mut pos = self.ids_offset;
+        for _ in 0..self.id_count {
+            read_varint(bytes, &mut pos)?;
+        }
+        Ok(pos)
+    }
+
+    fn ids<'a>(&self, bytes: &'a [u8]) -> impl Iterator<Item = Result<Id>> + 'a {
+        let mut pos = self.ids_offset;
+        let mut prev = 0u64;
+        (0..self.id_count).map(move |_| {
+            let zigzag = read_varint(bytes, &mut pos)?;
+            let delta = (zigzag >> 1) as i64 ^ -((zigzag & 1) as i64);
+            prev = prev.wrapping_add_signed(delta);
+            Ok(Id(prev))
+        })
+    }
+
+    fn is_fresh(&self, bytes: &[u8], id: Id) -> Result<bool> {
+        // last block starting at or before the id
+        let (mut lo, mut hi) = (0, self.block_count);
+        while lo < hi {
+            let mid = (lo + hi) / 2;
+            if self.block(bytes, mid)?.0 <= id.0 {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        let Some(block) = lo.checked_sub(1) else {
+            return Ok(false);
+        };
+
+        for range in self.block_ranges(bytes, block)? {
+            let range = range?;
+            if range.from > id {
+                break;
+            }
+            if range.to >= id {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+}
+
+/// Checks the header, the checksum and that the ids fill the rest of the file, `O(file size)`.
+fn verify(bytes: &[u8]) -> Result<Header> {
+    let header = Header::parse(bytes)?;
+    if file_checksum(bytes) != header.checksum {
+        return Err(eyre!("checksum mismatch"));
+    }
+    if header.ids_end(bytes)? != bytes.len() {
+        return Err(eyre!("trailing bytes after the ids"));
+    }
+    Ok(header)
+}
+
+#[derive(Serialize, Deserialize)]
+struct JsonDatabase {
+    version: u16,
+    ranges: Vec<(u64, u64)>,
+    ids: Vec<u64>,
+    checksum: u64,
+}
+
+fn json_checksum(ranges: &[(u64, u64)], ids: &[u64]) -> u64 {
+    let bytes: Vec<u8> = ranges
+        .iter()
+        .flat_map(|(from, to)| [*from, *to])
+        .chain(ids.iter().copied())
+        .flat_map(u64::to_le_bytes)
+        .collect();
+    fnv1a(&bytes)
+}
+
+impl Database {
+    /// Builds a database from merged ranges, their source lines are unknown and set to 0.
+    fn from_merged(ranges: Vec<Interval<Id>>, ids: Vec<Id>) -> Self {
+        Self {
+            fresh: ranges.iter().copied().collect(),
+            fresh_range_lines: vec![0; ranges.len()],
+            fresh_ranges: ranges,
+            ids,
+            listeners: Default::default(),
+        }
+    }
+
+    /// Writes the merged ranges and the ids in the binary format described at the top of this file.
+    pub fn write_binary<W: Write>(&self, mut out: W) -> Result<()> {
+        let ranges: Vec<Interval<Id>> = self.fresh().iter().collect();
+        let block_count = ranges.len().div_ceil(BLOCK_SIZE);
+
+        let mut index = vec![];
+        let mut body = vec![];
+        let mut prev = 0;
+        for (idx, range) in ranges.iter().enumerate() {
+            if idx % BLOCK_SIZE == 0 {
+                index.extend(range.from.0.to_le_bytes());
+                index.extend((body.len() as u64).to_le_bytes());
+                prev = range.from.0;
+            }
+            write_varint(&mut body, range.from.0 - prev);
+            write_varint(&mut body, range.to.0 - range.from.0);
+            prev = range.to.0;
+        }
+
+        let ids_offset = HEADER_LEN + index.len() + body.len();
+        let mut prev = 0u64;
+        for id in self.ids.iter() {
+            let delta = id.0.wrapping_sub(prev) as i64;
+            write_varint(&mut body, ((delta << 1) ^ (delta >> 63)) as u64);
+            prev = id.0;
+        }
+
+        let mut file = Vec::with_capacity(HEADER_LEN + index.len() + body.len());
+        file.extend(MAGIC);
+        file.extend(VERSION.to_le_bytes());
+        file.extend(0u16.to_le_bytes());
+        for value in [
+            ranges.len(),
+            self.ids.len(),
+            block_count,
+            HEADER_LEN + block_count * 16,
+            ids_offset,
+        ] {
+            file.extend((value as u64).to_le_bytes());
+        }
+        file.extend([0; 8]);
+        file.extend(index);
+        file.extend(body);
+        seal(&mut file);
+
+        out.write_all(&file)?;
+        Ok(())
+    }
+
+    pub fn from_binary(bytes: &[u8]) -> Result<Self> {
+        let header = verify(bytes)?;
+        Ok(Self::from_merged(
+            header.ranges(bytes).collect::<Result<_>>()?,
+            header.ids(bytes).collect::<Result<_>>()?,
+        ))
+    }
+
+    pub fn to_json(&self) -> Result<String> {
+        let ranges: Vec<(u64, u64)> = self.fresh().iter().map(|r| (r.from.0, r.to.0)).collect();
+        let ids: Vec<u64> = self.ids.iter().map(|id| id.0).collect();
+
+        Ok(serde_json::to_string(&JsonDatabase {
+            version: VERSION,
+            checksum: json_checksum(&ranges, &ids),
+            ranges,
+            ids,
+        })?)
+    }
+
+    pub fn from_json(value: &str) -> Result<Self> {
+        let json: JsonDatabase = serde_json::from_str(value)?;
+        if json.version != VERSION {
+            return Err(eyre!("unsupported version {}", json.version));
+        }
+        if json_checksum(&json.ranges, &json.ids) != json.checksum {
+            return Err(eyre!("checksum mismatch"));
+        }
+
+        Ok(Self::from_merged(
+            json.ranges
+                .into_iter()
+                .map(|(from, to)| Interval {
+                    from: Id(from),
+                    to: Id(to),
+                })
+                .collect(),
+            json.ids.into_iter().map(Id).collect(),
+        ))
+    }
+}
+
+/// A binary database file queried in place, without decoding it up front.
+#[derive(Debug)]
+pub struct MappedDatabase {
+    map: Mmap,
+    header: Header,
+}
+
+impl MappedDatabase {
+    /// Maps the file and checks its header and checksum.
+    ///
+    /// The checks read the whole file once, so opening costs `O(file size)`;
+    /// lookups after that decode at most one block.
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = fs::File::open(path)?;
+        // SAFETY: the map is read-only; the file must not be changed while it's open
+        let map = unsafe { Mmap::map(&file)? };
+        let header = verify(&map)?;
+        Ok(Self { map, header })
+    }
+
+    pub fn is_fresh(&self, id: Id) -> Result<bool> {
+        self.header.is_fresh(&self.map, id)
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = Result<Interval<Id>>> + '_ {
+        self.header.ranges(&self.map)
+    }
+
+    pub fn ids(&self) -> impl Iterator<Item = Result<Id>> + '_ {
+        self.header.ids(&self.map)
+    }
+
+    pub fn to_database(&self) -> Result<Database> {
+        Database::from_binary(&self.map)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::recorded_input;
+
+    fn merged(db: &Database) -> Vec<Interval<Id>> {
+        db.fresh().iter().collect()
+    }
+
+    #[test]
+    fn binary_round_trip() -> Result<()> {
+        let (input, db, expected) = recorded_input(2)?;
+
+        let mut bytes = vec![];
+        db.write_binary(&mut bytes)?;
+        let loaded = Database::from_binary(&bytes)?;
+
+        assert_eq!(merged(&loaded), merged(&db));
+        assert_eq!(loaded.ids, db.ids);
+        assert_eq!(loaded.fresh().covered_len().to_string(), expected);
+        assert!(bytes.len() < input.len() / 2);
+
+        let mut corrupt = bytes.clone();
+        *corrupt.last_mut().unwrap() ^= 1;
+        assert!(Database::from_binary(&corrupt).is_err());
+        corrupt = bytes.clone();
+        corrupt[4] = 9;
+        assert!(Database::from_binary(&corrupt).is_err());
+
+        // the reserved field isn't read, only the checksum covers it
+        corrupt = bytes.clone();
+        corrupt[6] ^= 1;
+        assert!(Database::from_binary(&corrupt).is_err());
+
+        corrupt = bytes.clone();
+        corrupt.push(0);
+        seal(&mut corrupt);
+        assert!(Database::from_binary(&corrupt).is_err());
+
+        // offsets that don't fit must fail, not wrap around
+        for pos in [40, HEADER_LEN + 8] {
+            corrupt = bytes.clone();
+            corrupt[pos..pos + 8].copy_from_slice(&u64::MAX.to_le_bytes());
+            seal(&mut corrupt);
+            assert!(Database::from_binary(&corrupt).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn json_round_trip() -> Result<()> {
+        let db = Database::from_input(include_str!("testinput.txt"))?;
+        let json = db.to_json()?;
+        let loaded = Database::from_json(&json)?;
+
+        assert_eq!(merged(&loaded), merged(&db));
+        assert_eq!(loaded.ids, db.ids);
+        assert!(Database::from_json(&json.replace("[3,5]", "[3,6]")).is_err());
+        Ok(())
+    }
+
+    // removes the file again even when an assertion fails
+    struct TempFile(std::path::PathBuf);
+
+    impl Drop for TempFile {
+        fn drop(&mut self) {
+            let _ = fs::remove_file(&self.0);
+        }
+    }
+
+    #[test]
+    fn mapped_queries() -> Result<()> {
+        let mut db = Database::from_input(include_str!("testinput.txt"))?;
+        // enough extra ranges for several blocks, plus one at the very end of the id space
+        for i in 0..200 {
+            db.insert_range(Interval {
+                from: Id(i * 10),
+                to: Id(i * 10 + 3),
+            })?;
+        }
+        db.insert_range(Interval {
+            from: Id(u64::MAX - 1),
+            to: Id(u64::MAX),
+        })?;
+
+        let file =
+            TempFile(std::env::temp_dir().join(format!("day05-store-{}.bin", std::process::id())));
+        db.write_binary(fs::File::create(&file.0)?)?;
+        let mapped = MappedDatabase::open(&file.0)?;
+
+        for id in db
+            .ids
+            .iter()
+            .copied()
+            .chain([0, 3, 4, 1999, 2000, u64::MAX].map(Id))
+        {
+            assert_eq!(mapped.is_fresh(id)?, db.is_fresh(id), "{:?}", id);
+        }
+        assert_eq!(mapped.ranges().collect::<Result<Vec<_>>>()?, merged(&db));
+        assert_eq!(mapped.ids().collect::<Result<Vec<_>>>()?, db.ids);
+        Ok(())
+    }
+}
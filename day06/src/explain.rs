@@ -0,0 +1,194 @@
+use std::{error::Error, fmt, ops::Range, str::FromStr};
+
+use aoc_core::table;
+use eyre::Result;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    Homework, Op, SolveError,
+    numeric::{Fault, Numeric},
+    reading::ReadingOrder,
+};
+
+/// How one task was read and solved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Step<T> {
+    /// Character columns of the task in the source, operator included.
+    pub columns: Range<usize>,
+    /// In reading order.
+    pub numbers: Vec<T>,
+    pub op: Op,
+    pub result: Result<T, Fault>,
+    /// Sum of the results so far, stays an error once a task or the sum fails.
+    pub total: Result<T, SolveError>,
+}
+
+/// Task by task account of a worksheet total, printed as a table by `Display`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation<T> {
+    pub steps: Vec<Step<T>>,
+}
+
+// values go out as strings, so big integers and fractions survive any JSON reader
+#[derive(Serialize)]
+struct JsonStep {
+    task: usize,
+    columns: (usize, usize),
+    op: String,
+    numbers: Vec<String>,
+    result: Option<String>,
+    error: Option<String>,
+    total: Option<String>,
+}
+
+impl<T> Homework<T>
+where
+    T: Numeric + FromStr,
+    <T as FromStr>::Err: Send + Sync + Error + 'static,
+{
+    /// Parses a worksheet and solves it one task at a time.
+    pub fn explain(input: &str, order: ReadingOrder) -> Result<Explanation<T>> {
+        let (hw, spans) = Self::from_input_with_spans(input, order)?;
+
+        let mut total = Ok(T::zero());
+        let steps = hw
+            .tasks
+            .into_iter()
+            .zip(spans)
+            .enumerate()
+            .map(|(idx, (task, columns))| {
+                let result = task.solve();
+                total = match (&total, &result) {
+                    (Err(e), _) => Err(*e),
+                    (_, Err(fault)) => Err(SolveError::Task(idx, *fault)),
+                    (Ok(acc), Ok(res)) => acc.add(res).map_err(|_| SolveError::Total),
+                };
+                Step {
+                    columns,
+                    numbers: task.numbers,
+                    op: task.op,
+                    result,
+                    total: total.clone(),
+                }
+            })
+            .collect();
+
+        Ok(Explanation { steps })
+    }
+
+    pub fn explain_part_one(input: &str) -> Result<Explanation<T>> {
+        Self::explain(input, ReadingOrder::PART_ONE)
+    }
+
+    pub fn explain_part_two(input: &str) -> Result<Explanation<T>> {
+        Self::explain(input, ReadingOrder::PART_TWO)
+    }
+}
+
+impl<T: fmt::Display> Explanation<T> {
+    /// The steps as a JSON array, one object per task, every value a string.
+    pub fn to_json(&self) -> Result<String> {
+        let steps = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| JsonStep {
+                task: idx,
+                columns: (step.columns.start, step.columns.end),
+                op: step.op.to_string(),
+                numbers: step.numbers.iter().map(T::to_string).collect(),
+                result: step.result.as_ref().ok().map(T::to_string),
+                error: step.result.as_ref().err().map(Fault::to_string),
+                total: step.total.as_ref().ok().map(T::to_string),
+            })
+            .collect_vec();
+        Ok(serde_json::to_string_pretty(&steps)?)
+    }
+}
+
+impl<T: fmt::Display> fmt::Display for Explanation<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self
+            .steps
+            .iter()
+            .enumerate()
+            .map(|(idx, step)| {
+                [
+                    idx.to_string(),
+                    format!("{:?}", step.columns),
+                    step.op.to_string(),
+                    step.numbers.iter().join(" "),
+                    match &step.result {
+                        Ok(res) => res.to_string(),
+                        Err(fault) => format!("error: {}", fault),
+                    },
+                    match &step.total {
+                        Ok(total) => total.to_string(),
+                        Err(_) => "-".to_string(),
+                    },
+                ]
+            })
+            .collect_vec();
+
+        f.write_str(&table::render(
+            ["task", "columns", "op", "numbers", "result", "total"],
+            &rows,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_table() -> Result<()> {
+        let ex = Homework::<i64>::explain_part_one(include_str!("testinput.txt"))?;
+        assert_eq!(
+            ex.to_string(),
+            "\
+task  columns  op  numbers     result   total
+0     0..3     *   123 45 6    33210    33210
+1     4..7     +   328 64 98   490      33700
+2     8..11    *   51 387 215  4243455  4277155
+3     12..15   +   64 23 314   401      4277556
+"
+        );
+
+        let ex = Homework::<i64>::explain_part_two(include_str!("testinput.txt"))?;
+        assert_eq!(
+            ex.steps.iter().map(|s| s.columns.clone()).collect_vec(),
+            vec![12..15, 8..11, 4..7, 0..3]
+        );
+        assert_eq!(ex.steps.last().map(|s| s.total), Some(Ok(3263827)));
+        Ok(())
+    }
+
+    #[test]
+    fn faults_and_json() -> Result<()> {
+        let ex = Homework::<u8>::explain_part_one("7 200 1\n0 2 3\n/ + *")?;
+        assert_eq!(ex.steps[0].result, Err(Fault::DivisionByZero));
+        assert_eq!(
+            ex.steps[1].total,
+            Err(SolveError::Task(0, Fault::DivisionByZero))
+        );
+        assert!(ex.to_string().contains("error: division by zero"));
+
+        let json: serde_json::Value = serde_json::from_str(&ex.to_json()?)?;
+        assert_eq!(
+            json[1],
+            serde_json::json!({
+                "task": 1,
+                "columns": [2, 5],
+                "op": "+",
+                "numbers": ["200", "2"],
+                "result": "202",
+                "error": null,
+                "total": null,
+            })
+        );
+        assert_eq!(json[0]["error"], "division by zero");
+        Ok(())
+    }
+}
@@ -0,0 +1,164 @@
+use std::{error::Error, str::FromStr};
+
+use eyre::{Result, eyre};
+
+use crate::numeric::Numeric;
+
+/// Evaluates one worksheet cell such as `12`, `2*3+1`, `2^3^2` or `max(4,7-5)`.
+///
+/// Usual precedence: `^` binds tightest and groups to the right, then `*`, `/`
+/// and `%`, then `+` and `-`, all grouping to the left.
+pub fn eval<T>(expr: &str) -> Result<T>
+where
+    T: Numeric + FromStr,
+    <T as FromStr>::Err: Send + Sync + Error + 'static,
+{
+    let mut parser = Parser { expr, pos: 0 };
+    let value = parser.sum()?;
+    match parser.peek() {
+        None => Ok(value),
+        Some(c) => Err(eyre!("unexpected '{}' at {} in {}", c, parser.pos, expr)),
+    }
+}
+
+struct Parser<'a> {
+    expr: &'a str,
+    pos: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<char> {
+        self.expr[self.pos..].chars().next()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        let found = self.peek() == Some(c);
+        if found {
+            self.pos += c.len_utf8();
+        }
+        found
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if !self.eat(c) {
+            return Err(eyre!("expected '{}' at {} in {}", c, self.pos, self.expr));
+        }
+        Ok(())
+    }
+
+    fn sum<T>(&mut self) -> Result<T>
+    where
+        T: Numeric + FromStr,
+        <T as FromStr>::Err: Send + Sync + Error + 'static,
+    {
+        let mut acc: T = self.product()?;
+        loop {
+            acc = if self.eat('+') {
+                acc.add(&self.product()?)?
+            } else if self.eat('-') {
+                acc.sub(&self.product()?)?
+            } else {
+                return Ok(acc);
+            };
+        }
+    }
+
+    fn product<T>(&mut self) -> Result<T>
+    where
+        T: Numeric + FromStr,
+        <T as FromStr>::Err: Send + Sync + Error + 'static,
+    {
+        let mut acc: T = self.power()?;
+        loop {
+            acc = if self.eat('*') {
+                acc.mul(&self.power()?)?
+            } else if self.eat('/') {
+                acc.div(&self.power()?)?
+            } else if self.eat('%') {
+                acc.rem(&self.power()?)?
+            } else {
+                return Ok(acc);
+            };
+        }
+    }
+
+    fn power<T>(&mut self) -> Result<T>
+    where
+        T: Numeric + FromStr,
+        <T as FromStr>::Err: Send + Sync + Error + 'static,
+    {
+        let base: T = self.atom()?;
+        if self.eat('^') {
+            return Ok(base.pow(&self.power()?)?);
+        }
+        Ok(base)
+    }
+
+    fn atom<T>(&mut self) -> Result<T>
+    where
+        T: Numeric + FromStr,
+        <T as FromStr>::Err: Send + Sync + Error + 'static,
+    {
+        if self.eat('(') {
+            let value = self.sum()?;
+            self.expect(')')?;
+            return Ok(value);
+        }
+
+        let rest = &self.expr[self.pos..];
+        for (name, pick_rhs) in [("min", T::gt as fn(&T, &T) -> bool), ("max", T::lt)] {
+            if rest.starts_with(name) {
+                self.pos += name.len();
+                self.expect('(')?;
+                let mut acc: T = self.sum()?;
+                while self.eat(',') {
+                    let rhs = self.sum()?;
+                    if pick_rhs(&acc, &rhs) {
+                        acc = rhs;
+                    }
+                }
+                self.expect(')')?;
+                return Ok(acc);
+            }
+        }
+
+        // a leading '-' belongs to the literal, so negative numbers work for signed types
+        let len = rest
+            .char_indices()
+            .find(|(i, c)| !(c.is_ascii_alphanumeric() || *c == '.' || (*i == 0 && *c == '-')))
+            .map_or(rest.len(), |(i, _)| i);
+        if len == 0 {
+            return Err(eyre!("expected a number at {} in {}", self.pos, self.expr));
+        }
+        self.pos += len;
+        Ok(rest[..len].parse()?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::numeric::Fault;
+
+    #[test]
+    fn precedence_and_grouping() -> Result<()> {
+        assert_eq!(eval::<i64>("12")?, 12);
+        assert_eq!(eval::<i64>("2+3*4")?, 14);
+        assert_eq!(eval::<i64>("(2+3)*4")?, 20);
+        assert_eq!(eval::<i64>("20-5-3")?, 12);
+        assert_eq!(eval::<i64>("100/10/5")?, 2);
+        assert_eq!(eval::<i64>("2^3^2")?, 512);
+        assert_eq!(eval::<i64>("17%5*2")?, 4);
+        assert_eq!(eval::<i64>("max(4,7-5,min(9,6))")?, 6);
+        assert_eq!(eval::<i64>("-3*-2")?, 6);
+
+        assert!(eval::<i64>("2+").is_err());
+        assert!(eval::<i64>("(2").is_err());
+        assert!(eval::<i64>("2)").is_err());
+        assert_eq!(
+            eval::<u32>("4/(2-2)").unwrap_err().downcast::<Fault>()?,
+            Fault::DivisionByZero
+        );
+        Ok(())
+    }
+}
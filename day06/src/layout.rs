@@ -0,0 +1,200 @@
+use std::{error::Error, fmt, ops::Range};
+
+use eyre::Result;
+use itertools::Itertools;
+
+use crate::Op;
+
+/// Character columns of one task and the op written under them.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Slot {
+    pub columns: Range<usize>,
+    pub op: Op,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LayoutIssue {
+    /// Numbers without an operator under them.
+    MissingOperator { columns: Range<usize> },
+    /// Several operators under one block of numbers, so the task boundary can't be told.
+    Ambiguous {
+        columns: Range<usize>,
+        ops: Vec<usize>,
+    },
+    /// Operator that doesn't start at the first column of its numbers.
+    Misaligned { op: usize, columns: Range<usize> },
+    /// Operator under a column that is blank in every number line.
+    Stray { op: usize },
+    /// Something on the operator line that isn't an operator.
+    Unknown { op: usize, token: String },
+}
+
+impl fmt::Display for LayoutIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutIssue::MissingOperator { columns } => {
+                write!(f, "columns {:?}: no operator", columns)
+            }
+            LayoutIssue::Ambiguous { columns, ops } => write!(
+                f,
+                "columns {:?}: operators at {} share one block of numbers",
+                columns,
+                ops.iter().join(", ")
+            ),
+            LayoutIssue::Misaligned { op, columns } => write!(
+                f,
+                "column {}: operator should be at column {}",
+                op, columns.start
+            ),
+            LayoutIssue::Stray { op } => write!(f, "column {}: operator under blank column", op),
+            LayoutIssue::Unknown { op, token } => {
+                write!(f, "column {}: unknown operator {}", op, token)
+            }
+        }
+    }
+}
+
+/// Every issue found in a worksheet.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayoutError(pub Vec<LayoutIssue>);
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "bad column layout: {}", self.0.iter().join("; "))
+    }
+}
+
+impl Error for LayoutError {}
+
+/// Splits the number lines into tasks at columns that are blank in every line, then
+/// checks that each task has exactly one operator starting at its first column.
+///
+/// Short lines count as padded with spaces, so stripped trailing whitespace is fine.
+pub fn detect(lines: &[&str], ops_line: &str) -> Result<Vec<Slot>, LayoutError> {
+    let rows = lines.iter().map(|l| l.chars().collect_vec()).collect_vec();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    let blank = |c: usize| rows.iter().all(|r| r.get(c).is_none_or(|ch| *ch == ' '));
+
+    let blocks = (0..width)
+        .chunk_by(|c| blank(*c))
+        .into_iter()
+        .filter(|(is_blank, _)| !is_blank)
+        .map(|(_, mut cols)| {
+            let start = cols.next().unwrap_or_default();
+            start..cols.last().unwrap_or(start) + 1
+        })
+        .collect_vec();
+
+    let mut ops = vec![];
+    let mut issues = vec![];
+    let mut start = 0;
+    for tok in ops_line.split(' ') {
+        if !tok.is_empty() {
+            match tok.parse::<Op>() {
+                Ok(op) => ops.push((start, op)),
+                Err(_) => issues.push(LayoutIssue::Unknown {
+                    op: start,
+                    token: tok.to_string(),
+                }),
+            }
+        }
+        start += tok.chars().count() + 1;
+    }
+
+    let mut slots = vec![];
+    let mut ops = ops.into_iter().peekable();
+    for block in blocks {
+        while let Some((op, _)) = ops.next_if(|(op, _)| *op < block.start) {
+            issues.push(LayoutIssue::Stray { op });
+        }
+        let under = std::iter::from_fn(|| ops.next_if(|(op, _)| *op < block.end)).collect_vec();
+
+        match under[..] {
+            [] => issues.push(LayoutIssue::MissingOperator { columns: block }),
+            [(pos, op)] if pos == block.start => slots.push(Slot { columns: block, op }),
+            [(pos, _)] => issues.push(LayoutIssue::Misaligned {
+                op: pos,
+                columns: block,
+            }),
+            _ => issues.push(LayoutIssue::Ambiguous {
+                columns: block,
+                ops: under.iter().map(|(pos, _)| *pos).collect(),
+            }),
+        }
+    }
+    issues.extend(ops.map(|(op, _)| LayoutIssue::Stray { op }));
+
+    if !issues.is_empty() {
+        return Err(LayoutError(issues));
+    }
+    Ok(slots)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn detect_input(input: &str) -> Result<Vec<Slot>, LayoutError> {
+        let mut lines = input.lines().collect_vec();
+        let ops_line = lines.pop().unwrap_or_default();
+        detect(&lines, ops_line)
+    }
+
+    #[test]
+    fn example_layout() {
+        let slots = detect_input(include_str!("testinput.txt")).unwrap();
+        assert_eq!(
+            slots.iter().map(|s| s.columns.clone()).collect_vec(),
+            vec![0..3, 4..7, 8..11, 12..15]
+        );
+        assert_eq!(
+            slots.iter().map(|s| s.op).collect_vec(),
+            vec![Op::Mul, Op::Add, Op::Mul, Op::Add]
+        );
+    }
+
+    #[test]
+    fn ragged_lines() {
+        let slots = detect_input("12  3\n4   56\n+   *").unwrap();
+        assert_eq!(
+            slots.iter().map(|s| s.columns.clone()).collect_vec(),
+            vec![0..2, 4..6]
+        );
+    }
+
+    #[test]
+    fn reports_positions() {
+        use LayoutIssue::*;
+
+        let issues = |input| detect_input(input).unwrap_err().0;
+        assert_eq!(
+            issues("12 34\n1  3\n +  *"),
+            vec![
+                Misaligned {
+                    op: 1,
+                    columns: 0..2
+                },
+                Misaligned {
+                    op: 4,
+                    columns: 3..5
+                }
+            ]
+        );
+        assert_eq!(
+            issues("1234\n1 34\n+ *"),
+            vec![Ambiguous {
+                columns: 0..4,
+                ops: vec![0, 2]
+            }]
+        );
+        assert_eq!(
+            issues("12  34\n+ * "),
+            vec![Stray { op: 2 }, MissingOperator { columns: 4..6 }]
+        );
+        assert_eq!(issues("1\n+ *"), vec![Stray { op: 2 }]);
+        assert_eq!(
+            detect_input("12 3\n+  ?").unwrap_err().to_string(),
+            "bad column layout: column 3: unknown operator ?; columns 3..4: no operator"
+        );
+    }
+}
@@ -0,0 +1,434 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use aoc_core::Solution;
+use eyre::{Result, eyre};
+use itertools::Itertools;
+use num_bigint::BigInt;
+
+use crate::{
+    numeric::{Fault, Numeric},
+    reading::ReadingOrder,
+};
+
+pub mod explain;
+pub mod expr;
+pub mod layout;
+pub mod numeric;
+pub mod reading;
+pub mod render;
+
+pub use aoc_core::grid;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Add,
+    Mul,
+    Sub,
+    Div,
+    Rem,
+    Pow,
+    Min,
+    Max,
+}
+
+/// Which end of the numbers a task starts folding from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fold {
+    /// `a - b - c` is `(a - b) - c`.
+    Left,
+    /// `a ^ b ^ c` is `a ^ (b ^ c)`.
+    Right,
+}
+
+impl Op {
+    pub fn fold(&self) -> Fold {
+        match self {
+            Op::Pow => Fold::Right,
+            _ => Fold::Left,
+        }
+    }
+
+    pub fn apply<T: Numeric>(&self, lhs: &T, rhs: &T) -> Result<T, Fault> {
+        match self {
+            Op::Add => lhs.add(rhs),
+            Op::Mul => lhs.mul(rhs),
+            Op::Sub => lhs.sub(rhs),
+            Op::Div => lhs.div(rhs),
+            Op::Rem => lhs.rem(rhs),
+            Op::Pow => lhs.pow(rhs),
+            Op::Min if rhs < lhs => Ok(rhs.clone()),
+            Op::Max if rhs > lhs => Ok(rhs.clone()),
+            Op::Min | Op::Max => Ok(lhs.clone()),
+        }
+    }
+
+    /// Result for a task without numbers, only for ops that have one.
+    fn identity<T: Numeric>(&self) -> Option<T> {
+        match self {
+            Op::Add => Some(T::zero()),
+            Op::Mul => Some(T::one()),
+            _ => None,
+        }
+    }
+}
+
+impl TryFrom<char> for Op {
+    type Error = eyre::ErrReport;
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Ok(match c {
+            '+' => Op::Add,
+            '*' => Op::Mul,
+            '-' => Op::Sub,
+            '/' => Op::Div,
+            '%' => Op::Rem,
+            '^' => Op::Pow,
+            _ => return Err(eyre!("invalid operator")),
+        })
+    }
+}
+
+impl FromStr for Op {
+    type Err = eyre::ErrReport;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "min" => Ok(Op::Min),
+            "max" => Ok(Op::Max),
+            _ => match s.chars().collect_vec()[..] {
+                [c] => Op::try_from(c),
+                _ => Err(eyre!("invalid operator {}", s)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Op {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Op::Add => "+",
+            Op::Mul => "*",
+            Op::Sub => "-",
+            Op::Div => "/",
+            Op::Rem => "%",
+            Op::Pow => "^",
+            Op::Min => "min",
+            Op::Max => "max",
+        };
+        f.pad(s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathTask<T> {
+    pub numbers: Vec<T>,
+    pub op: Op,
+}
+
+impl<T: Numeric> MathTask<T> {
+    /// Folds the numbers with the op, in the direction given by `Op::fold`.
+    pub fn solve(&self) -> Result<T, Fault> {
+        let mut numbers: Box<dyn Iterator<Item = &T>> = match self.op.fold() {
+            Fold::Left => Box::new(self.numbers.iter()),
+            Fold::Right => Box::new(self.numbers.iter().rev()),
+        };
+
+        let Some(first) = numbers.next() else {
+            return self.op.identity().ok_or(Fault::Empty);
+        };
+        numbers.try_fold(first.clone(), |acc, num| match self.op.fold() {
+            Fold::Left => self.op.apply(&acc, num),
+            Fold::Right => self.op.apply(num, &acc),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SolveError {
+    /// The task at this index has no result.
+    Task(usize, Fault),
+    /// Every task has a result, their sum overflows.
+    Total,
+}
+
+impl SolveError {
+    /// Whether a wider type could get past this error.
+    pub fn is_overflow(&self) -> bool {
+        matches!(
+            self,
+            SolveError::Task(_, Fault::Overflow) | SolveError::Total
+        )
+    }
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Task(idx, fault) => write!(f, "task {}: {}", idx, fault),
+            SolveError::Total => write!(f, "total overflows"),
+        }
+    }
+}
+
+impl Error for SolveError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Homework<T> {
+    pub tasks: Vec<MathTask<T>>,
+}
+
+impl<T> Homework<T> {
+    /// Sum of all task results, or the first task that has none.
+    pub fn total(&self) -> Result<T, SolveError>
+    where
+        T: Numeric,
+    {
+        self.tasks
+            .iter()
+            .enumerate()
+            .try_fold(T::zero(), |acc, (idx, task)| {
+                let res = task.solve().map_err(|fault| SolveError::Task(idx, fault))?;
+                acc.add(&res).map_err(|_| SolveError::Total)
+            })
+    }
+
+    /// Same as `total`, but redoes the sum in `W` when `T` overflows.
+    pub fn total_widening<W>(&self) -> Result<W, SolveError>
+    where
+        T: Numeric,
+        W: Numeric + From<T>,
+    {
+        match self.total() {
+            Ok(total) => Ok(total.into()),
+            Err(e) if e.is_overflow() => self.widen::<W>().total(),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn widen<W>(&self) -> Homework<W>
+    where
+        T: Clone,
+        W: From<T>,
+    {
+        Homework {
+            tasks: self
+                .tasks
+                .iter()
+                .map(|task| MathTask {
+                    numbers: task.numbers.iter().cloned().map(W::from).collect(),
+                    op: task.op,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<T> Homework<T>
+where
+    T: Numeric + FromStr,
+    <T as FromStr>::Err: Send + Sync + Error + 'static,
+{
+    pub fn from_input_part_one(input: &str) -> Result<Self> {
+        Self::from_input_with_order(input, ReadingOrder::PART_ONE)
+    }
+
+    pub fn from_input_part_two(input: &str) -> Result<Self> {
+        Self::from_input_with_order(input, ReadingOrder::PART_TWO)
+    }
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn part_one(&self, input: &str) -> Result<String> {
+        let hw = Homework::<i64>::from_input_part_one(input)?;
+        Ok(hw.total_widening::<BigInt>()?.to_string())
+    }
+
+    fn part_two(&self, input: &str) -> Result<String> {
+        let hw = Homework::<i64>::from_input_part_two(input)?;
+        Ok(hw.total_widening::<BigInt>()?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use aoc_core::answers;
+    use num_bigint::BigInt;
+
+    use super::*;
+    use crate::numeric::{Mod, Rational};
+
+    const SRC: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/src");
+
+    #[test]
+    fn part_one_example() -> Result<()> {
+        let hw = Homework::<i32>::from_input_part_one(include_str!("testinput.txt"))?;
+        let res = hw.total()?;
+        assert_eq!(res, 4277556);
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_solution() -> Result<()> {
+        let (input, expected) = answers::recorded(Path::new(SRC), 6, "input", 1)?;
+        let hw = Homework::<i64>::from_input_part_one(&input)?;
+        let res = hw.total()?;
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_example() -> Result<()> {
+        let hw = Homework::<i32>::from_input_part_two(include_str!("testinput.txt"))?;
+        let res = hw.total()?;
+        assert_eq!(res, 3263827);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_solution() -> Result<()> {
+        let (input, expected) = answers::recorded(Path::new(SRC), 6, "input", 2)?;
+        let hw = Homework::<i64>::from_input_part_two(&input)?;
+        let res = hw.total()?;
+        assert_eq!(res.to_string(), expected);
+        Ok(())
+    }
+
+    #[test]
+    fn overflow_names_the_task() -> Result<()> {
+        let (input, expected) = answers::recorded(Path::new(SRC), 6, "input", 1)?;
+        let hw = Homework::<i32>::from_input_part_one(&input)?;
+        let Err(SolveError::Task(idx, Fault::Overflow)) = hw.total() else {
+            panic!("expected a task to overflow i32");
+        };
+        assert!(hw.tasks[..idx].iter().all(|task| task.solve().is_ok()));
+        assert_eq!(hw.tasks[idx].solve(), Err(Fault::Overflow));
+        assert_eq!(hw.total_widening::<i64>()?.to_string(), expected);
+
+        let tiny = Homework::<u8>::from_input_part_one("100 100\n100 100\n+   +")?;
+        assert_eq!(tiny.total(), Err(SolveError::Total));
+        assert_eq!(tiny.total_widening::<u16>(), Ok(400));
+        Ok(())
+    }
+
+    #[test]
+    fn big_integers() -> Result<()> {
+        let (input, expected) = answers::recorded(Path::new(SRC), 6, "input", 2)?;
+        let hw = Homework::<BigInt>::from_input_part_two(&input)?;
+        assert_eq!(hw.total()?.to_string(), expected);
+
+        // squared numbers push the products past u64
+        let mut narrow = Homework::<u64>::from_input_part_two(&input)?;
+        for task in narrow.tasks.iter_mut() {
+            task.numbers = task.numbers.iter().map(|n| n * n).collect();
+        }
+        let wide = narrow.widen::<BigInt>();
+
+        assert!(matches!(
+            narrow.total(),
+            Err(SolveError::Task(_, Fault::Overflow))
+        ));
+        assert_eq!(narrow.total_widening::<BigInt>()?, wide.total()?);
+        Ok(())
+    }
+
+    #[test]
+    fn more_operators() -> Result<()> {
+        let input = "\
+100 2  3 17 9 4
+ 20 3  2  5 2 8
+  5 2 2  3 7 1
+  -   ^   /   %   min max ";
+        let hw = Homework::<i64>::from_input_part_one(input)?;
+        let results = hw
+            .tasks
+            .iter()
+            .map(|task| task.solve())
+            .collect::<Result<Vec<_>, _>>()?;
+        // 100 - 20 - 5, 2 ^ (3 ^ 2), (3 / 2) / 2 ...
+        assert_eq!(results, vec![75, 512, 0, 2, 2, 8]);
+
+        let hw = Homework::<u32>::from_input_part_one("8 4\n2 0\n/ /")?;
+        assert_eq!(
+            hw.total_widening::<u64>(),
+            Err(SolveError::Task(1, Fault::DivisionByZero))
+        );
+        assert_eq!(
+            hw.total().unwrap_err().to_string(),
+            "task 1: division by zero"
+        );
+
+        let empty = MathTask::<i32> {
+            numbers: vec![],
+            op: Op::Sub,
+        };
+        assert_eq!(empty.solve(), Err(Fault::Empty));
+        Ok(())
+    }
+
+    #[test]
+    fn row_expressions() -> Result<()> {
+        let hw = Homework::<i64>::from_input_part_one("2*3 10\n4 max(5,9)-1\n+ *")?;
+        assert_eq!(hw.total()?, 10 + 80);
+
+        let err = Homework::<i64>::from_input_part_one("1 2/0\n+ +").unwrap_err();
+        assert!(err.to_string().starts_with("task 1:"), "{}", err);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_operator_words() -> Result<()> {
+        let input = "\
+123 4  
+45  567
+min max";
+        let hw = Homework::<i32>::from_input_part_two(input)?;
+        assert_eq!(hw.tasks[0].numbers, vec![7, 6, 45]);
+        assert_eq!(hw.tasks[0].op, Op::Max);
+        assert_eq!(hw.tasks[1].numbers, vec![3, 25, 14]);
+        assert_eq!(hw.total()?, 45 + 3);
+        Ok(())
+    }
+
+    #[test]
+    fn part_two_stripped_trailing_spaces() -> Result<()> {
+        let input = include_str!("testinput.txt")
+            .lines()
+            .map(str::trim_end)
+            .join("\n");
+        let hw = Homework::<i32>::from_input_part_two(&input)?;
+        assert_eq!(hw.total()?, 3263827);
+        Ok(())
+    }
+
+    #[test]
+    fn other_number_types() -> Result<()> {
+        type M = Mod<1_000_000_007>;
+
+        let (input, part_one) = answers::recorded(Path::new(SRC), 6, "input", 1)?;
+        let (_, part_two) = answers::recorded(Path::new(SRC), 6, "input", 2)?;
+        let hw = Homework::<M>::from_input_part_one(&input)?;
+        assert_eq!(hw.total()?, part_one.parse()?);
+        let hw = Homework::<M>::from_input_part_two(&input)?;
+        assert_eq!(hw.total()?, part_two.parse()?);
+
+        let hw = Homework::<Rational>::from_input_part_one("1/2 0.25\n1/3 2\n+ /")?;
+        assert_eq!(hw.total()?, "23/24".parse()?);
+        assert_eq!(Homework::from_input_part_one(&hw.render_part_one()?)?, hw);
+
+        let hw = Homework::<f64>::from_input_part_one("1.5 2\n2.5 0.5\n+ ^")?;
+        assert_eq!(hw.total()?, 4.0 + 2f64.sqrt());
+        Ok(())
+    }
+
+    #[test]
+    fn part_one_missing_number() {
+        let err = Homework::<i32>::from_input_part_one("1 2 3\n4 5\n+ + +").unwrap_err();
+        assert_eq!(err.to_string(), "task 2: no number on line 2");
+    }
+}
@@ -0,0 +1,435 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{
+    CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Signed, ToPrimitive, Zero, checked_pow,
+};
+
+/// Why a single operation has no result.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Fault {
+    Overflow,
+    DivisionByZero,
+    /// Negative exponent, the result wouldn't be an integer.
+    NegativeExponent,
+    /// Operator without an identity applied to no numbers.
+    Empty,
+    /// The operation has no meaning for the number type, like `%` on residues.
+    Undefined,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Fault::Overflow => write!(f, "overflow"),
+            Fault::DivisionByZero => write!(f, "division by zero"),
+            Fault::NegativeExponent => write!(f, "negative exponent"),
+            Fault::Empty => write!(f, "no numbers"),
+            Fault::Undefined => write!(f, "undefined for this number type"),
+        }
+    }
+}
+
+impl Error for Fault {}
+
+/// Text that `Mod` or `Rational` can't read as a number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InvalidNumber(pub String);
+
+impl fmt::Display for InvalidNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid number {}", self.0)
+    }
+}
+
+impl Error for InvalidNumber {}
+
+/// Numbers a worksheet can be solved in.
+///
+/// Every operation reports failures instead of panicking or wrapping.
+pub trait Numeric: Clone + PartialOrd {
+    /// Additive identity, the sum of no numbers.
+    fn zero() -> Self;
+    /// Multiplicative identity, the product of no numbers.
+    fn one() -> Self;
+
+    fn add(&self, rhs: &Self) -> Result<Self, Fault>;
+    fn sub(&self, rhs: &Self) -> Result<Self, Fault>;
+    fn mul(&self, rhs: &Self) -> Result<Self, Fault>;
+    fn div(&self, rhs: &Self) -> Result<Self, Fault>;
+    fn rem(&self, rhs: &Self) -> Result<Self, Fault>;
+    fn pow(&self, exp: &Self) -> Result<Self, Fault>;
+}
+
+fn checked<T: Zero>(rhs: &T, f: impl FnOnce() -> Option<T>) -> Result<T, Fault> {
+    if rhs.is_zero() {
+        return Err(Fault::DivisionByZero);
+    }
+    f().ok_or(Fault::Overflow)
+}
+
+macro_rules! impl_numeric {
+    ($($t:ty),*) => {$(
+        impl Numeric for $t {
+            fn zero() -> Self {
+                Zero::zero()
+            }
+
+            fn one() -> Self {
+                One::one()
+            }
+
+            fn add(&self, rhs: &Self) -> Result<Self, Fault> {
+                CheckedAdd::checked_add(self, rhs).ok_or(Fault::Overflow)
+            }
+
+            fn sub(&self, rhs: &Self) -> Result<Self, Fault> {
+                CheckedSub::checked_sub(self, rhs).ok_or(Fault::Overflow)
+            }
+
+            fn mul(&self, rhs: &Self) -> Result<Self, Fault> {
+                CheckedMul::checked_mul(self, rhs).ok_or(Fault::Overflow)
+            }
+
+            fn div(&self, rhs: &Self) -> Result<Self, Fault> {
+                checked(rhs, || CheckedDiv::checked_div(self, rhs))
+            }
+
+            fn rem(&self, rhs: &Self) -> Result<Self, Fault> {
+                // via division, BigInt has no `CheckedRem`
+                checked(rhs, || {
+                    CheckedDiv::checked_div(self, rhs).map(|q| self - &(q * rhs))
+                })
+            }
+
+            fn pow(&self, exp: &Self) -> Result<Self, Fault> {
+                if *exp < <Self as Numeric>::zero() {
+                    return Err(Fault::NegativeExponent);
+                }
+                let exp = exp.to_usize().ok_or(Fault::Overflow)?;
+                checked_pow(self.clone(), exp).ok_or(Fault::Overflow)
+            }
+        }
+    )*};
+}
+
+impl_numeric!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, BigInt
+);
+
+/// Residue modulo `P`.
+///
+/// Division works for divisors coprime to `P`, so for every nonzero one when
+/// `P` is prime. `P` below 2 is rejected at compile time.
+///
+/// Ordering compares the residues, so `min` and `max` pick by residue.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mod<const P: u64>(u64);
+
+impl<const P: u64> Mod<P> {
+    const MODULUS_CHECK: () = assert!(P >= 2, "Mod needs a modulus of at least 2");
+
+    pub fn new(value: u64) -> Self {
+        let () = Self::MODULUS_CHECK;
+        Self(value % P)
+    }
+
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+
+    /// Extended Euclid, `None` when the residue shares a factor with `P`.
+    fn inverse(&self) -> Option<Self> {
+        let (mut r0, mut r1) = (P as i128, self.0 as i128);
+        let (mut t0, mut t1) = (0i128, 1i128);
+        while r1 != 0 {
+            let q = r0 / r1;
+            (r0, r1) = (r1, r0 - q * r1);
+            (t0, t1) = (t1, t0 - q * t1);
+        }
+        (r0 == 1).then(|| Self(t0.rem_euclid(P as i128) as u64))
+    }
+
+    fn mul_mod(&self, rhs: &Self) -> Self {
+        Self((self.0 as u128 * rhs.0 as u128 % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Numeric for Mod<P> {
+    fn zero() -> Self {
+        Self::new(0)
+    }
+
+    fn one() -> Self {
+        Self::new(1)
+    }
+
+    fn add(&self, rhs: &Self) -> Result<Self, Fault> {
+        Ok(Self(((self.0 as u128 + rhs.0 as u128) % P as u128) as u64))
+    }
+
+    fn sub(&self, rhs: &Self) -> Result<Self, Fault> {
+        Ok(Self(
+            ((self.0 as u128 + P as u128 - rhs.0 as u128) % P as u128) as u64,
+        ))
+    }
+
+    fn mul(&self, rhs: &Self) -> Result<Self, Fault> {
+        Ok(self.mul_mod(rhs))
+    }
+
+    fn div(&self, rhs: &Self) -> Result<Self, Fault> {
+        if rhs.0 == 0 {
+            return Err(Fault::DivisionByZero);
+        }
+        Ok(self.mul_mod(&rhs.inverse().ok_or(Fault::Undefined)?))
+    }
+
+    fn rem(&self, _: &Self) -> Result<Self, Fault> {
+        Err(Fault::Undefined)
+    }
+
+    /// Undefined, the exponent is only known modulo `P` while the power depends on it modulo `P - 1`.
+    fn pow(&self, _: &Self) -> Result<Self, Fault> {
+        Err(Fault::Undefined)
+    }
+}
+
+/// Reduces arbitrarily long decimal numbers, negative ones included.
+impl<const P: u64> FromStr for Mod<P> {
+    type Err = InvalidNumber;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let () = Self::MODULUS_CHECK;
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(digits) => (true, digits),
+            None => (false, s),
+        };
+        let invalid = || InvalidNumber(s.to_string());
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+
+        let value = digits.chars().try_fold(0, |acc, c| {
+            let digit = c.to_digit(10).ok_or_else(invalid)?;
+            Ok(((acc as u128 * 10 + digit as u128) % P as u128) as u64)
+        })?;
+
+        Ok(match negative {
+            true => Self((P - value) % P),
+            false => Self(value),
+        })
+    }
+}
+
+impl<const P: u64> fmt::Display for Mod<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl<const P: u64> From<u64> for Mod<P> {
+    fn from(value: u64) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Exact fraction with unbounded numerator and denominator.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Rational(pub BigRational);
+
+impl Numeric for Rational {
+    fn zero() -> Self {
+        Self(Zero::zero())
+    }
+
+    fn one() -> Self {
+        Self(One::one())
+    }
+
+    fn add(&self, rhs: &Self) -> Result<Self, Fault> {
+        Ok(Self(&self.0 + &rhs.0))
+    }
+
+    fn sub(&self, rhs: &Self) -> Result<Self, Fault> {
+        Ok(Self(&self.0 - &rhs.0))
+    }
+
+    fn mul(&self, rhs: &Self) -> Result<Self, Fault> {
+        Ok(Self(&self.0 * &rhs.0))
+    }
+
+    fn div(&self, rhs: &Self) -> Result<Self, Fault> {
+        checked(&rhs.0, || Some(&self.0 / &rhs.0)).map(Self)
+    }
+
+    /// Truncated like the integer `%`, `7/2 % 1` is `1/2`.
+    fn rem(&self, rhs: &Self) -> Result<Self, Fault> {
+        checked(&rhs.0, || Some(&self.0 % &rhs.0)).map(Self)
+    }
+
+    /// Only whole exponents, negative ones give the reciprocal.
+    fn pow(&self, exp: &Self) -> Result<Self, Fault> {
+        if !exp.0.is_integer() {
+            return Err(Fault::Undefined);
+        }
+        if self.0.is_zero() && exp.0.is_negative() {
+            return Err(Fault::DivisionByZero);
+        }
+        let exp = exp.0.to_integer().to_i32().ok_or(Fault::Overflow)?;
+        Ok(Self(num_traits::Pow::pow(&self.0, exp)))
+    }
+}
+
+/// Takes integers, fractions like `3/4` and decimals like `-1.25`.
+impl FromStr for Rational {
+    type Err = InvalidNumber;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidNumber(s.to_string());
+        let Some((int, frac)) = s.split_once('.') else {
+            return s.parse().map(Self).map_err(|_| invalid());
+        };
+        if frac.starts_with(['-', '+']) {
+            return Err(invalid());
+        }
+
+        let scale = BigInt::from(10).pow(frac.len() as u32);
+        let digits: BigInt = format!("{}{}", int, frac).parse().map_err(|_| invalid())?;
+        Ok(Self(BigRational::new(digits, scale)))
+    }
+}
+
+impl fmt::Display for Rational {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl From<i64> for Rational {
+    fn from(value: i64) -> Self {
+        Self(BigRational::from_integer(value.into()))
+    }
+}
+
+/// Floats report infinite results as overflow and have no integer restriction on `^`.
+impl Numeric for f64 {
+    fn zero() -> Self {
+        0.0
+    }
+
+    fn one() -> Self {
+        1.0
+    }
+
+    fn add(&self, rhs: &Self) -> Result<Self, Fault> {
+        finite(self + rhs)
+    }
+
+    fn sub(&self, rhs: &Self) -> Result<Self, Fault> {
+        finite(self - rhs)
+    }
+
+    fn mul(&self, rhs: &Self) -> Result<Self, Fault> {
+        finite(self * rhs)
+    }
+
+    fn div(&self, rhs: &Self) -> Result<Self, Fault> {
+        checked(rhs, || Some(self / rhs)).and_then(finite)
+    }
+
+    fn rem(&self, rhs: &Self) -> Result<Self, Fault> {
+        checked(rhs, || Some(self % rhs)).and_then(finite)
+    }
+
+    fn pow(&self, exp: &Self) -> Result<Self, Fault> {
+        if *self == 0.0 && *exp < 0.0 {
+            return Err(Fault::DivisionByZero);
+        }
+        match self.powf(*exp) {
+            res if res.is_nan() => Err(Fault::Undefined),
+            res => finite(res),
+        }
+    }
+}
+
+fn finite(value: f64) -> Result<f64, Fault> {
+    match value.is_finite() {
+        true => Ok(value),
+        false => Err(Fault::Overflow),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type M = Mod<1_000_000_007>;
+
+    #[test]
+    fn faults() {
+        assert_eq!(Numeric::div(&7i32, &0), Err(Fault::DivisionByZero));
+        assert_eq!(Numeric::rem(&7u8, &0), Err(Fault::DivisionByZero));
+        assert_eq!(Numeric::div(&i32::MIN, &-1), Err(Fault::Overflow));
+        assert_eq!(Numeric::sub(&3u8, &4), Err(Fault::Overflow));
+        assert_eq!(Numeric::pow(&2i64, &-1), Err(Fault::NegativeExponent));
+        assert_eq!(Numeric::pow(&2i64, &62), Ok(1 << 62));
+        assert_eq!(Numeric::pow(&2i64, &63), Err(Fault::Overflow));
+        assert_eq!(
+            Numeric::pow(&BigInt::from(2), &BigInt::from(100)),
+            Ok(BigInt::from(1u128 << 100))
+        );
+    }
+
+    #[test]
+    fn modular() -> eyre::Result<()> {
+        let m = |s: &str| s.parse::<M>();
+        assert_eq!(m("1000000008")?, M::new(1));
+        assert_eq!(m("-1")?, M::new(1_000_000_006));
+        assert_eq!(m("123456789012345678901234567890")?, M::new(197_434_842));
+        assert!(m("12a").is_err());
+
+        let three = M::new(3);
+        let third = M::one().div(&three)?;
+        assert_eq!(third.mul(&three)?, M::one());
+        // 2^(P + 1) and 2^1 have the same exponent residue but different values
+        assert_eq!(M::new(2).pow(&M::new(1)), Err(Fault::Undefined));
+        assert_eq!(three.div(&M::zero()), Err(Fault::DivisionByZero));
+        assert_eq!(three.rem(&three), Err(Fault::Undefined));
+
+        assert_eq!(Mod::<2>::new(3).div(&Mod::new(5))?, Mod::new(1));
+        assert_eq!(M::new(1_000_000_006).div(&M::new(1_000_000_006))?, M::one());
+
+        // composite modulus: only residues coprime to it can be divided by
+        assert_eq!(Mod::<4>::one().div(&Mod::new(2)), Err(Fault::Undefined));
+        assert_eq!(Mod::<4>::one().div(&Mod::new(3))?, Mod::new(3));
+        assert_eq!(Mod::<10>::new(7).div(&Mod::new(5)), Err(Fault::Undefined));
+        assert_eq!(Mod::<10>::new(7).div(&Mod::new(9))?, Mod::new(3));
+        let big = Mod::<{ u64::MAX }>::new(u64::MAX - 1);
+        assert_eq!(big.div(&big)?, Mod::one());
+        assert_eq!("-1".parse::<Mod<2>>()?, Mod::new(1));
+        Ok(())
+    }
+
+    #[test]
+    fn rationals() -> eyre::Result<()> {
+        let r = |s: &str| s.parse::<Rational>();
+        assert_eq!(r("-1.25")?, r("-5/4")?);
+        assert_eq!(r("0.5")?.add(&r("1/3")?)?, r("5/6")?);
+        assert_eq!(r("2/3")?.pow(&r("-2")?)?, r("9/4")?);
+        assert_eq!(r("7/2")?.rem(&r("1")?)?, r("1/2")?);
+        assert_eq!(r("2")?.pow(&r("1/2")?), Err(Fault::Undefined));
+        assert_eq!(r("1")?.div(&Rational::zero()), Err(Fault::DivisionByZero));
+        assert!(r("1.-5").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn floats() {
+        assert_eq!(Numeric::pow(&2.0, &0.5), Ok(2f64.sqrt()));
+        assert_eq!(Numeric::div(&1.0, &0.0), Err(Fault::DivisionByZero));
+        assert_eq!(Numeric::mul(&f64::MAX, &2.0), Err(Fault::Overflow));
+        assert_eq!(Numeric::pow(&-8.0, &0.5), Err(Fault::Undefined));
+    }
+}
@@ -0,0 +1,305 @@
+use std::{error::Error, ops::Range, str::FromStr};
+
+use eyre::{Result, eyre};
+use itertools::Itertools;
+
+use aoc_core::grid::transpose;
+
+use crate::{Homework, MathTask, Op, expr, layout, numeric::Numeric};
+
+/// Whether a number is written along a line or down a column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Numbers {
+    /// Whitespace separated cells, one number per line and task.
+    Rows,
+    /// One number per character column, tasks split at blank columns.
+    Columns,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Horizontal {
+    LeftToRight,
+    RightToLeft,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Vertical {
+    TopDown,
+    BottomUp,
+}
+
+/// How to read a worksheet, like the writing direction of a script.
+///
+/// Tasks always come in `horizontal` order. Row numbers are read as written,
+/// signs and all, and follow each other in `vertical` order. Column numbers
+/// have their digits in `vertical` order and follow each other in `horizontal`
+/// order.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReadingOrder {
+    pub numbers: Numbers,
+    pub horizontal: Horizontal,
+    pub vertical: Vertical,
+}
+
+impl ReadingOrder {
+    pub const PART_ONE: Self = Self {
+        numbers: Numbers::Rows,
+        horizontal: Horizontal::LeftToRight,
+        vertical: Vertical::TopDown,
+    };
+
+    pub const PART_TWO: Self = Self {
+        numbers: Numbers::Columns,
+        horizontal: Horizontal::RightToLeft,
+        vertical: Vertical::TopDown,
+    };
+
+    fn along<I>(&self, it: I) -> Box<dyn Iterator<Item = I::Item>>
+    where
+        I: DoubleEndedIterator + 'static,
+    {
+        match self.horizontal {
+            Horizontal::LeftToRight => Box::new(it),
+            Horizontal::RightToLeft => Box::new(it.rev()),
+        }
+    }
+
+    fn down<I>(&self, it: I) -> Box<dyn Iterator<Item = I::Item>>
+    where
+        I: DoubleEndedIterator + 'static,
+    {
+        match self.vertical {
+            Vertical::TopDown => Box::new(it),
+            Vertical::BottomUp => Box::new(it.rev()),
+        }
+    }
+}
+
+impl<T> Homework<T>
+where
+    T: Numeric + FromStr,
+    <T as FromStr>::Err: Send + Sync + Error + 'static,
+{
+    /// Parses a worksheet whose last line holds the operators.
+    pub fn from_input_with_order(input: &str, order: ReadingOrder) -> Result<Self> {
+        Ok(Self::from_input_with_spans(input, order)?.0)
+    }
+
+    /// Like `from_input_with_order`, also returns the character columns each task covers.
+    pub(crate) fn from_input_with_spans(
+        input: &str,
+        order: ReadingOrder,
+    ) -> Result<(Self, Vec<Range<usize>>)> {
+        let mut lines: Vec<&str> = input.lines().filter(|l| !l.trim().is_empty()).collect();
+
+        let ops_line = lines
+            .pop()
+            .ok_or(eyre!("need at least one line in input"))?;
+
+        // text of every number, grouped by task, in reading order
+        let tasks: Vec<(Range<usize>, Op, Vec<String>)> = match order.numbers {
+            Numbers::Rows => {
+                let ops = tokens(ops_line)
+                    .into_iter()
+                    .map(|(span, tok)| Ok((span, Op::from_str(&tok)?)))
+                    .collect::<Result<Vec<_>>>()?;
+                let cells = lines.iter().map(|l| tokens(l)).collect_vec();
+
+                let columns = transpose(cells)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(idx, cells)| {
+                        let (span, op) = ops.get(idx).cloned().ok_or(eyre::format_err!(
+                            "index {} out of bounds for ops vector",
+                            idx
+                        ))?;
+                        let cells = cells
+                            .into_iter()
+                            .enumerate()
+                            .map(|(line, cell)| {
+                                cell.ok_or(eyre!("task {}: no number on line {}", idx, line + 1))
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        let span = cells.iter().fold(span, |span, (cell, _)| {
+                            span.start.min(cell.start)..span.end.max(cell.end)
+                        });
+                        let numbers = order
+                            .down(cells.into_iter())
+                            .map(|(_, cell)| cell)
+                            .collect();
+                        Ok((span, op, numbers))
+                    });
+                order
+                    .along(columns.collect::<Result<Vec<_>>>()?.into_iter())
+                    .collect()
+            }
+            Numbers::Columns => {
+                let slots = layout::detect(&lines, ops_line)?;
+                let rows = lines
+                    .iter()
+                    .map(|line| line.chars().collect_vec())
+                    .collect_vec();
+
+                order
+                    .along(slots.into_iter())
+                    .map(|slot| {
+                        let numbers = order
+                            .along(slot.columns.clone())
+                            .map(|c| {
+                                let column = rows
+                                    .iter()
+                                    .map(|row| row.get(c).copied().unwrap_or(' '))
+                                    .collect_vec();
+                                order.down(column.into_iter()).collect::<String>()
+                            })
+                            .collect();
+                        (slot.columns, slot.op, numbers)
+                    })
+                    .collect()
+            }
+        };
+
+        // row cells may hold expressions like `2*3`, they get evaluated before the column op
+        let (tasks, spans) = tasks
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (span, op, numbers))| {
+                let task = MathTask {
+                    numbers: numbers
+                        .iter()
+                        .map(|n| expr::eval(n.trim()).map_err(|e| eyre!("task {}: {}", idx, e)))
+                        .collect::<Result<_>>()?,
+                    op,
+                };
+                Ok((task, span))
+            })
+            .collect::<Result<(Vec<MathTask<T>>, Vec<_>)>>()?;
+
+        Ok((Self { tasks }, spans))
+    }
+}
+
+/// Whitespace separated tokens and the character columns they cover.
+fn tokens(line: &str) -> Vec<(Range<usize>, String)> {
+    line.chars()
+        .enumerate()
+        .chunk_by(|(_, c)| c.is_whitespace())
+        .into_iter()
+        .filter(|(is_blank, _)| !is_blank)
+        .map(|(_, chars)| {
+            let chars = chars.collect_vec();
+            let start = chars.first().map_or(0, |(c, _)| *c);
+            (
+                start..start + chars.len(),
+                chars.into_iter().map(|(_, ch)| ch).collect(),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn all_orders() -> Result<()> {
+        use Horizontal::*;
+        use Numbers::*;
+        use Vertical::*;
+
+        let input = "12 3\n4  56\n+  *";
+        let cases = [
+            (
+                Rows,
+                LeftToRight,
+                TopDown,
+                [(vec![12, 4], Op::Add), (vec![3, 56], Op::Mul)],
+            ),
+            (
+                Rows,
+                LeftToRight,
+                BottomUp,
+                [(vec![4, 12], Op::Add), (vec![56, 3], Op::Mul)],
+            ),
+            (
+                Rows,
+                RightToLeft,
+                TopDown,
+                [(vec![3, 56], Op::Mul), (vec![12, 4], Op::Add)],
+            ),
+            (
+                Rows,
+                RightToLeft,
+                BottomUp,
+                [(vec![56, 3], Op::Mul), (vec![4, 12], Op::Add)],
+            ),
+            (
+                Columns,
+                LeftToRight,
+                TopDown,
+                [(vec![14, 2], Op::Add), (vec![35, 6], Op::Mul)],
+            ),
+            (
+                Columns,
+                LeftToRight,
+                BottomUp,
+                [(vec![41, 2], Op::Add), (vec![53, 6], Op::Mul)],
+            ),
+            (
+                Columns,
+                RightToLeft,
+                TopDown,
+                [(vec![6, 35], Op::Mul), (vec![2, 14], Op::Add)],
+            ),
+            (
+                Columns,
+                RightToLeft,
+                BottomUp,
+                [(vec![6, 53], Op::Mul), (vec![2, 41], Op::Add)],
+            ),
+        ];
+
+        for (numbers, horizontal, vertical, expected) in cases {
+            let order = ReadingOrder {
+                numbers,
+                horizontal,
+                vertical,
+            };
+            let hw = Homework::<u32>::from_input_with_order(input, order)?;
+            let expected = expected
+                .into_iter()
+                .map(|(numbers, op)| MathTask { numbers, op })
+                .collect_vec();
+            assert_eq!(hw.tasks, expected, "{:?}", order);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn right_to_left_rows_keep_cells_whole() -> Result<()> {
+        let order = ReadingOrder {
+            horizontal: Horizontal::RightToLeft,
+            ..ReadingOrder::PART_ONE
+        };
+        let hw = Homework::<i64>::from_input_with_order(
+            "-3 max(5,9)
+12 2*-4
++  *",
+            order,
+        )?;
+        assert_eq!(
+            hw.tasks,
+            vec![
+                MathTask {
+                    numbers: vec![9, -8],
+                    op: Op::Mul
+                },
+                MathTask {
+                    numbers: vec![-3, 12],
+                    op: Op::Add
+                },
+            ]
+        );
+        Ok(())
+    }
+}
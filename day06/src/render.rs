@@ -0,0 +1,237 @@
+use std::fmt::Display;
+
+use eyre::{Result, eyre};
+use itertools::Itertools;
+
+use crate::{
+    Homework,
+    reading::{Horizontal, Numbers, ReadingOrder, Vertical},
+};
+
+/// One task laid out as a rectangle of characters above its op.
+struct Block {
+    rows: Vec<Vec<char>>,
+    op: String,
+}
+
+impl Block {
+    fn width(&self) -> usize {
+        self.rows
+            .iter()
+            .map(|r| r.len())
+            .chain([self.op.chars().count()])
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+fn reverse_if<T>(cond: bool, mut v: Vec<T>) -> Vec<T> {
+    if cond {
+        v.reverse();
+    }
+    v
+}
+
+impl<T: Display> Homework<T> {
+    /// Writes the tasks as a worksheet that `from_input_with_order` reads back with the same order.
+    pub fn render(&self, order: ReadingOrder) -> Result<String> {
+        let rtl = order.horizontal == Horizontal::RightToLeft;
+        let bottom_up = order.vertical == Vertical::BottomUp;
+
+        let height = match order.numbers {
+            Numbers::Rows => self.tasks.first().map_or(0, |t| t.numbers.len()),
+            Numbers::Columns => self
+                .tasks
+                .iter()
+                .flat_map(|t| t.numbers.iter())
+                .map(|n| n.to_string().chars().count())
+                .max()
+                .unwrap_or(0),
+        };
+
+        let mut blocks = vec![];
+        for (idx, task) in self.tasks.iter().enumerate() {
+            if task.numbers.is_empty() {
+                return Err(eyre!("task {} has no numbers", idx));
+            }
+            let texts = task
+                .numbers
+                .iter()
+                .map(|n| n.to_string().chars().collect_vec());
+
+            let rows = match order.numbers {
+                Numbers::Rows => {
+                    if task.numbers.len() != height {
+                        return Err(eyre!(
+                            "task {} has {} numbers, row layouts need {} like the first task",
+                            idx,
+                            task.numbers.len(),
+                            height
+                        ));
+                    }
+                    reverse_if(bottom_up, texts.collect())
+                }
+                Numbers::Columns => {
+                    let columns = reverse_if(
+                        rtl,
+                        texts
+                            .map(|t| {
+                                let mut column = reverse_if(bottom_up, t);
+                                column.resize(height, ' ');
+                                column
+                            })
+                            .collect(),
+                    );
+                    (0..height)
+                        .map(|r| columns.iter().map(|c: &Vec<char>| c[r]).collect())
+                        .collect()
+                }
+            };
+
+            blocks.push(Block {
+                rows,
+                op: task.op.to_string(),
+            });
+        }
+        if rtl {
+            blocks.reverse();
+        }
+
+        let widths = blocks.iter().map(Block::width).collect_vec();
+        let mut lines = (0..height)
+            .map(|r| {
+                blocks
+                    .iter()
+                    .zip(&widths)
+                    .map(|(block, width)| format!("{:width$}", String::from_iter(&block.rows[r])))
+                    .join(" ")
+            })
+            .collect_vec();
+        lines.push(
+            blocks
+                .iter()
+                .zip(&widths)
+                .map(|(block, width)| format!("{:width$}", block.op))
+                .join(" "),
+        );
+
+        Ok(lines.join("\n") + "\n")
+    }
+
+    pub fn render_part_one(&self) -> Result<String> {
+        self.render(ReadingOrder::PART_ONE)
+    }
+
+    pub fn render_part_two(&self) -> Result<String> {
+        self.render(ReadingOrder::PART_TWO)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{Rng, SeedableRng, rngs::StdRng};
+
+    use super::*;
+    use crate::{MathTask, Op};
+
+    const OPS: [Op; 8] = [
+        Op::Add,
+        Op::Mul,
+        Op::Sub,
+        Op::Div,
+        Op::Rem,
+        Op::Pow,
+        Op::Min,
+        Op::Max,
+    ];
+
+    #[test]
+    fn example_layouts() -> Result<()> {
+        let hw = Homework::<i32>::from_input_part_one(include_str!("testinput.txt"))?;
+        assert_eq!(
+            hw.render_part_one()?,
+            "123 328 51  64 \n45  64  387 23 \n6   98  215 314\n*   +   *   +  \n"
+        );
+
+        let hw = Homework::<i32>::from_input_part_two(include_str!("testinput.txt"))?;
+        assert_eq!(Homework::from_input_part_two(&hw.render_part_two()?)?, hw);
+        Ok(())
+    }
+
+    #[test]
+    fn rejects_unrenderable() {
+        let task = |numbers: Vec<i32>| MathTask {
+            numbers,
+            op: Op::Add,
+        };
+        let ragged = Homework {
+            tasks: vec![task(vec![1, 2]), task(vec![3])],
+        };
+        assert!(ragged.render_part_one().is_err());
+        assert!(ragged.render_part_two().is_ok());
+
+        let empty = Homework {
+            tasks: vec![task(vec![])],
+        };
+        assert!(empty.render_part_two().is_err());
+    }
+
+    #[test]
+    fn parse_render_round_trip() -> Result<()> {
+        use Horizontal::*;
+        use Numbers::*;
+        use Vertical::*;
+
+        let mut rng = StdRng::seed_from_u64(44);
+
+        for _ in 0..300 {
+            let rows = rng.random_range(1..6);
+            let tasks = (0..rng.random_range(1..10))
+                .map(|_| MathTask {
+                    numbers: (0..rng.random_range(1..6))
+                        .map(|_| {
+                            let digits = rng.random_range(1..12);
+                            rng.random_range(-(10i64.pow(digits))..10i64.pow(digits))
+                        })
+                        .collect_vec(),
+                    op: OPS[rng.random_range(0..OPS.len())],
+                })
+                .collect_vec();
+            let same_height = Homework {
+                tasks: tasks
+                    .iter()
+                    .map(|t| MathTask {
+                        numbers: t.numbers.iter().copied().cycle().take(rows).collect(),
+                        op: t.op,
+                    })
+                    .collect(),
+            };
+            let hw = Homework { tasks };
+
+            for numbers in [Rows, Columns] {
+                for horizontal in [LeftToRight, RightToLeft] {
+                    for vertical in [TopDown, BottomUp] {
+                        let order = ReadingOrder {
+                            numbers,
+                            horizontal,
+                            vertical,
+                        };
+                        let hw = match numbers {
+                            Rows => &same_height,
+                            Columns => &hw,
+                        };
+                        let text = hw.render(order)?;
+                        assert_eq!(
+                            &Homework::from_input_with_order(&text, order)?,
+                            hw,
+                            "{:?}\n{}",
+                            order,
+                            text
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}